@@ -138,6 +138,10 @@
 #![forbid(unsafe_code)]
 #![cfg_attr(crossfig_no_core, feature(no_core))]
 #![cfg_attr(crossfig_no_core, no_core)]
+#![cfg_attr(all(test, crossfig_nightly), feature(cfg_version))]
+
+#[cfg(test)]
+extern crate std;
 
 /// Provides a `match`-like expression similar to [`cfg_if`] and based on the experimental
 /// [`cfg_match`].
@@ -149,8 +153,13 @@
 /// - a `cfg(...)` pattern (e.g., `feature = "foo"`)
 /// - a wildcard `_`
 /// - an alias defined using [`alias`]
+/// - an alias of an optional dependency, `dep(feature = "foo", foo::alias)`, which is only named
+///   if the feature enabling that dependency is active
+/// - a compiler version, `version("1.80")`, or the presence of a path, `accessible(core::hint)`
+///   (see [Unstable Predicates](#unstable-predicates))
 ///
-/// Note that aliases are evaluated from the context of the defining crate, not the consumer.
+/// Note that aliases are evaluated from the context of the defining crate, not the consumer,
+/// unless they are marked `#[call_site]`.
 /// This allows a library to export aliases for use in consuming crates.
 ///
 /// # Examples
@@ -178,6 +187,135 @@
 /// }
 /// ```
 ///
+///
+/// # Preferences
+///
+/// When several arms are enabled, the first always wins.
+/// To allow choosing between them at build time, an arm can be given a label as a string literal,
+/// and then moved to the front by building with `--cfg crossfig_prefer="label"`.
+///
+/// ```
+/// # extern crate crossfig;
+/// # use crossfig::{switch, alias};
+/// # alias! {
+/// #    parking_lot: { all() },
+/// #    std: { all() },
+/// # }
+/// #
+/// switch! {
+///     "parking_lot": parking_lot => {
+///         // Used by default.
+///     }
+///     "std": std => {
+///         // Used with `--cfg crossfig_prefer="std"`.
+///     }
+///     _ => {}
+/// }
+/// # fn main() {}
+/// ```
+///
+/// Preferring a label whose arm isn't enabled raises a compilation error.
+/// Since configuration options can't be enumerated, a preference matching none of the labels in a
+/// switch is ignored by it.
+/// As the preference is checked by the crate using the switch, that crate should declare it to
+/// avoid `unexpected_cfgs` warnings:
+///
+/// ```toml
+/// [lints.rust.unexpected_cfgs]
+/// level = "warn"
+/// check-cfg = ['cfg(crossfig_prefer, values(any()))']
+/// ```
+///
+/// # Unstable Predicates
+///
+/// Arms can test the version of the compiler with `version("1.80")`, or whether a path exists
+/// with `accessible(core::hint::black_box)`, without a build script.
+/// Since the equivalent `cfg` predicates are unstable, these arms are inactive by default, falling
+/// through to the arms after them, which should provide a conservative fallback.
+///
+/// ```
+/// # #![cfg_attr(crossfig_nightly, feature(cfg_version))]
+/// # extern crate crossfig;
+/// # use crossfig::switch;
+/// switch! {
+///     version("1.80") => {
+///         // Used on a nightly compiler since 1.80, with `--cfg crossfig_nightly`.
+///     }
+///     _ => {
+///         // Used otherwise.
+///     }
+/// }
+/// # fn main() {}
+/// ```
+///
+/// Building with `--cfg crossfig_nightly` maps `version(...)` to `cfg(version(...))`, which is
+/// evaluated by the crate using the switch, so that crate must enable `#![feature(cfg_version)]`.
+/// `accessible(...)` is always inactive, since the compiler only provides `#[cfg_accessible(...)]`,
+/// which can't select an alternative when the path doesn't exist.
+///
+/// # Unreachable Arms
+///
+/// Arms which can never be selected raise a compilation error.
+/// This includes arms after a wildcard, and arms after `all()` or [`enabled`].
+/// A wildcard is still allowed after an unconditional arm, so a fallback can be left in place,
+/// and labelled arms are not checked, since they can be reached through a preference.
+///
+/// ```compile_fail
+/// # extern crate crossfig;
+/// # use crossfig::{switch, enabled};
+/// switch! {
+///     enabled => {}
+///     #[cfg(unix)] => {}
+/// }
+/// // error: patterns after `enabled` are unreachable: `#[cfg(unix)]`
+/// # fn main() {}
+/// ```
+///
+/// Aliases are evaluated where they're defined, so an alias which merely happens to be enabled
+/// can't be told apart from one which is always enabled.
+/// Instead, [`alias`] marks aliases defined as exactly `all()` or [`enabled`], and the arms after
+/// those are reported too.
+///
+/// ```compile_fail
+/// # extern crate crossfig;
+/// # use crossfig::{switch, alias};
+/// alias! {
+///     always: { all() },
+/// }
+///
+/// switch! {
+///     always => {}
+///     #[cfg(unix)] => {}
+/// }
+/// // error: patterns after `always` are unreachable, as it is always enabled: `#[cfg(unix)]`
+/// # fn main() {}
+/// ```
+///
+/// When used as an expression, arms repeating the exact pattern of an earlier arm are reported as
+/// well.
+/// Comparing patterns requires defining a macro, which is only possible within the block of the
+/// expression, so repeated arms are not reported for switches over items, `impl` blocks, or
+/// `trait` blocks.
+/// Patterns are also compared token by token, so `any(a, b)` and `any(b, a)` are distinct.
+///
+/// ```compile_fail
+/// # extern crate crossfig;
+/// # use crossfig::{switch, alias};
+/// # alias! {
+/// #    std: { #[cfg(feature = "std")] },
+/// # }
+/// #
+/// # fn main() {
+/// let value = switch! {{
+///     std => { 1 }
+///     #[cfg(unix)] => { 2 }
+///     std => { 3 }
+///     _ => { 4 }
+/// }};
+/// // error: pattern `std` is unreachable, as it duplicates an earlier arm
+/// # }
+/// ```
+///
 /// [`cfg_if`]: https://crates.io/crates/cfg-if
 /// [`cfg_match`]: https://github.com/rust-lang/rust/issues/115585
 #[macro_export]
@@ -186,18 +324,305 @@ macro_rules! switch {
     () => {};
 
     // Allow switch!{{ ... }} to act as an expression in certain contexts
-    ({$($tt:tt)*}) => {
-        { $crate::switch! { $($tt)* } }
+    // A block can define the macros comparing each pattern, so repeated arms are also checked.
+    ({}) => {
+        {}
+    };
+    ({$($arms:tt)+}) => {
+        {
+            $crate::__switch_labels! {
+                @arms []
+                @labels []
+                @conds []
+                @duplicates [($)]
+                $($arms)+
+            }
+        }
+    };
+
+    ($($arms:tt)+) => {
+        $crate::__switch_labels! {
+            @arms []
+            @labels []
+            @conds []
+            @duplicates []
+            $($arms)+
+        }
+    };
+}
+
+/// Implementation detail of [`switch`].
+/// Removes any labels from the arms, moving a labelled arm to the front if it's preferred with
+/// `--cfg crossfig_prefer="label"`.
+/// The condition of each arm is also collected, to check for unreachable arms.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __switch_labels {
+    // # Preferences
+    // A single condition can't be unreachable, so no checks are needed.
+    (
+        @arms [$($arms:tt)*]
+        @labels []
+        @conds [$($cond:tt)?]
+        @duplicates $duplicates:tt
+    ) => {
+        $crate::__switch! { $($arms)* }
+    };
+    (
+        @arms [$($arms:tt)*]
+        @labels []
+        @conds $conds:tt
+        @duplicates $duplicates:tt
+    ) => {
+        $crate::__switch_check! { @unreachable $conds }
+        $crate::__switch_check! { @duplicates $duplicates $conds }
+        $crate::__switch! { $($arms)* }
+    };
+    (
+        @arms [$($arms:tt)*]
+        @labels [$([$label:tt [$($cond:tt)+] $output:tt])+]
+        @conds $conds:tt
+        @duplicates $duplicates:tt
+    ) => {
+        $crate::__switch_check! { @unreachable $conds }
+        $crate::__switch_check! { @duplicates $duplicates $conds }
+        $crate::__switch! {
+            $(
+                #[cfg(crossfig_prefer = $label)] => {
+                    $crate::__switch! {
+                        $($cond)+ => $output
+                        _ => {
+                            compile_error!(concat!("the preferred arm `", $label, "` is not enabled"));
+                        }
+                    }
+                }
+            )+
+            _ => {
+                $crate::__switch! { $($arms)* }
+            }
+        }
+    };
+
+    // # Arm
+    // Labelled arms can be preferred, so aren't checked for being unreachable.
+    (
+        @arms [$($arms:tt)*]
+        @labels [$($labels:tt)*]
+        @conds $conds:tt
+        @duplicates $duplicates:tt
+        @label [$label:tt]
+        @cond [$($cond:tt)+]
+        => $output:tt
+        $($rest:tt)*
+    ) => {
+        $crate::__switch_labels! {
+            @arms [$($arms)* $($cond)+ => $output]
+            @labels [$($labels)* [$label [$($cond)+] $output]]
+            @conds $conds
+            @duplicates $duplicates
+            $($rest)*
+        }
+    };
+    (
+        @arms [$($arms:tt)*]
+        @labels $labels:tt
+        @conds [$($conds:tt)*]
+        @duplicates $duplicates:tt
+        @label []
+        @cond [$($cond:tt)+]
+        => $output:tt
+        $($rest:tt)*
+    ) => {
+        $crate::__switch_labels! {
+            @arms [$($arms)* $($cond)+ => $output]
+            @labels $labels
+            @conds [$($conds)* ($($cond)+)]
+            @duplicates $duplicates
+            $($rest)*
+        }
+    };
+    (
+        @arms $arms:tt
+        @labels $labels:tt
+        @conds $conds:tt
+        @duplicates $duplicates:tt
+        @label $label:tt
+        @cond [$($cond:tt)*]
+        $token:tt $($rest:tt)*
+    ) => {
+        $crate::__switch_labels! {
+            @arms $arms
+            @labels $labels
+            @conds $conds
+            @duplicates $duplicates
+            @label $label
+            @cond [$($cond)* $token]
+            $($rest)*
+        }
+    };
+    // ## Labelled
+    (
+        @arms $arms:tt
+        @labels $labels:tt
+        @conds $conds:tt
+        @duplicates $duplicates:tt
+        $label:literal: $($rest:tt)+
+    ) => {
+        $crate::__switch_labels! {
+            @arms $arms
+            @labels $labels
+            @conds $conds
+            @duplicates $duplicates
+            @label [$label]
+            @cond []
+            $($rest)+
+        }
+    };
+    // ## Wildcard
+    (
+        @arms [$($arms:tt)*]
+        @labels $labels:tt
+        @conds $conds:tt
+        @duplicates $duplicates:tt
+        _ => $output:tt
+        $($rest:tt)*
+    ) => {
+        $crate::__switch_labels! {
+            @arms [$($arms)* _ => $output]
+            @labels $labels
+            @conds $conds
+            @duplicates $duplicates
+            $($rest)*
+        }
+    };
+    // ## Unlabelled
+    // Short conditions are moved a whole arm at a time, limiting recursion.
+    (
+        @arms [$($arms:tt)*]
+        @labels $labels:tt
+        @conds [$($conds:tt)*]
+        @duplicates $duplicates:tt
+        $cond:tt => $output:tt
+        $($rest:tt)*
+    ) => {
+        $crate::__switch_labels! {
+            @arms [$($arms)* $cond => $output]
+            @labels $labels
+            @conds [$($conds)* ($cond)]
+            @duplicates $duplicates
+            $($rest)*
+        }
+    };
+    (
+        @arms [$($arms:tt)*]
+        @labels $labels:tt
+        @conds [$($conds:tt)*]
+        @duplicates $duplicates:tt
+        $first:tt $second:tt => $output:tt
+        $($rest:tt)*
+    ) => {
+        $crate::__switch_labels! {
+            @arms [$($arms)* $first $second => $output]
+            @labels $labels
+            @conds [$($conds)* ($first $second)]
+            @duplicates $duplicates
+            $($rest)*
+        }
+    };
+    (
+        @arms $arms:tt
+        @labels $labels:tt
+        @conds $conds:tt
+        @duplicates $duplicates:tt
+        $($rest:tt)+
+    ) => {
+        $crate::__switch_labels! {
+            @arms $arms
+            @labels $labels
+            @conds $conds
+            @duplicates $duplicates
+            @label []
+            @cond []
+            $($rest)+
+        }
+    };
+}
+
+/// Implementation detail of [`switch`].
+/// Raises a compilation error for arms which can never be selected.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __switch_check {
+    // # Arms after an unconditional arm
+    // Wildcards aren't collected, so a trailing fallback is still accepted.
+    // Arms after a wildcard are already rejected by `__switch`.
+    (@unreachable [(all()) $($rest:tt)+]) => {
+        compile_error!(concat!("patterns after `all()` are unreachable:" $(, " `", stringify!$rest, "`")+));
+    };
+    (@unreachable [(enabled) $($rest:tt)+]) => {
+        compile_error!(concat!("patterns after `enabled` are unreachable:" $(, " `", stringify!$rest, "`")+));
+    };
+    (@unreachable [(crossfig::enabled) $($rest:tt)+]) => {
+        compile_error!(concat!("patterns after `crossfig::enabled` are unreachable:" $(, " `", stringify!$rest, "`")+));
+    };
+    (@unreachable [(::crossfig::enabled) $($rest:tt)+]) => {
+        compile_error!(concat!("patterns after `::crossfig::enabled` are unreachable:" $(, " `", stringify!$rest, "`")+));
+    };
+    (@unreachable [$first:tt $($rest:tt)+]) => {
+        $crate::__switch_check! { @query $first $first [$($rest)+] }
+        $crate::__switch_check! { @unreachable [$($rest)+] }
+    };
+    (@unreachable [$first:tt]) => {};
+    (@unreachable []) => {};
+
+    // # Arms after an unconditional alias
+    // Aliases which are always enabled expand the query into an error, while others discard it.
+    // The alias is parsed as a path, as in `__switch`, so deprecated aliases only warn once.
+    (@query ($(::)? $($segment:ident)::+) ($alias:path) $rest:tt) => {
+        $alias! {
+            if { $crate::__switch_check! { @unconditional [$alias] $rest } }
+            else {}
+        }
+    };
+    (@query $cond:tt $other:tt $rest:tt) => {};
+    (@unconditional $alias:tt $rest:tt) => {};
+
+    // # Duplicate Arms
+    // Each condition is compared to those after it by a macro matching exactly its tokens.
+    // Defining that macro is only possible within a block, so is skipped without a `$` to use.
+    (@duplicates [] $conds:tt) => {};
+    (@duplicates [($d:tt)] [($($first:tt)+) $($rest:tt)*]) => {
+        #[allow(unused_macros)]
+        macro_rules! __crossfig_duplicate_arm {
+            (($($first)+)) => {
+                compile_error!(concat!("pattern `", stringify!($($first)+), "` is unreachable, as it duplicates an earlier arm"));
+            };
+            ($d($d other:tt)*) => {};
+        }
+
+        $(__crossfig_duplicate_arm! { $rest })*
+
+        $crate::__switch_check! { @duplicates [($d)] [$($rest)*] }
     };
+    (@duplicates [($d:tt)] []) => {};
+}
+
+/// Implementation detail of [`switch`].
+/// Evaluates the arms top to bottom, once any labels have been removed.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __switch {
+    // Empty invocations should return nothing
+    () => {};
 
     // # Operation: not(...)
     (
         not($($args:tt)*) => $output:tt
         $($arms:tt)*
     ) => {
-        $crate::switch! {
+        $crate::__switch! {
             $($args)* => {
-                $crate::switch! { $($arms)* }
+                $crate::__switch! { $($arms)* }
             }
             _ => $output
         }
@@ -209,14 +634,14 @@ macro_rules! switch {
         all() => $output:tt
         $($arms:tt)*
     ) => {
-        $crate::switch! { _ => $output }
+        $crate::__switch! { _ => $output }
     };
     // ## Inner Op
     (
         all($op:ident($($cond:tt)*)) => $output:tt
         $($arms:tt)*
     ) => {
-        $crate::switch! {
+        $crate::__switch! {
             $op($($cond)*) => $output
             $($arms)*
         }
@@ -226,9 +651,9 @@ macro_rules! switch {
         all($op:ident($($cond:tt)*), $($rest:tt)*) => $output:tt
         $($arms:tt)*
     ) => {
-        $crate::switch! {
+        $crate::__switch! {
             $op($($cond)*) => {
-                $crate::switch! {
+                $crate::__switch! {
                     all($($rest)*) => $output
                     $($arms)*
                 }
@@ -241,7 +666,7 @@ macro_rules! switch {
         all(#[cfg($meta:meta)]) => $output:tt
         $($arms:tt)*
     ) => {
-        $crate::switch! {
+        $crate::__switch! {
             #[cfg($meta)] => $output
             $($arms)*
         }
@@ -251,9 +676,9 @@ macro_rules! switch {
         all(#[cfg($meta:meta)], $($rest:tt)*) => $output:tt
         $($arms:tt)*
     ) => {
-        $crate::switch! {
+        $crate::__switch! {
             #[cfg($meta)] => {
-                $crate::switch! {
+                $crate::__switch! {
                     all($($rest)*) => $output
                     $($arms)*
                 }
@@ -266,7 +691,7 @@ macro_rules! switch {
         all($cond:path) => $output:tt
         $($arms:tt)*
     ) => {
-        $crate::switch! {
+        $crate::__switch! {
             $cond => $output
             $($arms)*
         }
@@ -276,9 +701,9 @@ macro_rules! switch {
         all($cond:path, $($rest:tt)*) => $output:tt
         $($arms:tt)*
     ) => {
-        $crate::switch! {
+        $crate::__switch! {
             $cond => {
-                $crate::switch! {
+                $crate::__switch! {
                     all($($rest)*) => $output
                     $($arms)*
                 }
@@ -293,14 +718,14 @@ macro_rules! switch {
         any() => $output:tt
         $($arms:tt)*
     ) => {
-        $crate::switch! { $($arms)* }
+        $crate::__switch! { $($arms)* }
     };
     // ## Inner Op
     (
         any($op:ident($($cond:tt)*)) => $output:tt
         $($arms:tt)*
     ) => {
-        $crate::switch! {
+        $crate::__switch! {
             $op($($cond)*) => $output
             $($arms)*
         }
@@ -310,10 +735,10 @@ macro_rules! switch {
         any($op:ident($($cond:tt)*), $($rest:tt)*) => $output:tt
         $($arms:tt)*
     ) => {
-        $crate::switch! {
+        $crate::__switch! {
             $op($($cond)*) => $output
             _ => {
-                $crate::switch! {
+                $crate::__switch! {
                     any($($rest)*) => $output
                     $($arms)*
                 }
@@ -325,7 +750,7 @@ macro_rules! switch {
         any(#[cfg($meta:meta)]) => $output:tt
         $($arms:tt)*
     ) => {
-        $crate::switch! {
+        $crate::__switch! {
             #[cfg($meta)] => $output
             $($arms)*
         }
@@ -335,7 +760,7 @@ macro_rules! switch {
         any(#[cfg($meta:meta)], $($rest:tt)*) => $output:tt
         $($arms:tt)*
     ) => {
-        $crate::switch! {
+        $crate::__switch! {
             #[cfg($meta)] => $output
             any($($rest)*) => $output
             $($arms)*
@@ -346,7 +771,7 @@ macro_rules! switch {
         any($cond:path) => $output:tt
         $($arms:tt)*
     ) => {
-        $crate::switch! {
+        $crate::__switch! {
             $cond => $output
             $($arms)*
         }
@@ -356,13 +781,52 @@ macro_rules! switch {
         any($cond:path, $($rest:tt)*) => $output:tt
         $($arms:tt)*
     ) => {
-        $crate::switch! {
+        $crate::__switch! {
             $cond => $output
             any($($rest)*) => $output
             $($arms)*
         }
     };
 
+    // # Operation: version(...) and accessible(...)
+    (
+        version($($args:tt)*) => $output:tt
+        $($arms:tt)*
+    ) => {
+        $crate::__switch_unstable! {
+            version($($args)*) => $output
+            $($arms)*
+        }
+    };
+    (
+        accessible($($args:tt)*) => $output:tt
+        $($arms:tt)*
+    ) => {
+        $crate::__switch_unstable! {
+            accessible($($args)*) => $output
+            $($arms)*
+        }
+    };
+
+    // # Operation: dep(...)
+    // The feature is checked first, so the alias is only named if the dependency is present.
+    (
+        dep(feature = $feature:literal, $($alias:tt)+) => $output:tt
+        $($arms:tt)*
+    ) => {
+        $crate::__switch! {
+            all(#[cfg(feature = $feature)], $($alias)+) => $output
+            $($arms)*
+        }
+    };
+    // ## Common Mistake: missing feature or alias
+    (
+        dep($($args:tt)*) => $output:tt
+        $($arms:tt)*
+    ) => {
+        compile_error!(concat!("expected `dep(feature = \"name\", path::to::alias)`, found `dep(", stringify!($($args)*), ")`"));
+    };
+
     // # Wildcard Branch
     (
         _ => { $($output:tt)* }
@@ -383,10 +847,10 @@ macro_rules! switch {
         $($arms:tt)*
     ) => {
         #[cfg($meta)]
-        $crate::switch! { _ => $output }
+        $crate::__switch! { _ => $output }
 
         #[cfg(not($meta))]
-        $crate::switch! { $($arms)* }
+        $crate::__switch! { $($arms)* }
     };
 
     // # Alias Integration
@@ -395,64 +859,545 @@ macro_rules! switch {
         $($arms:tt)*
     ) => {
         $cond! {
-            if { $crate::switch! { _ => $output } }
-            else { $crate::switch! { $($arms)* } }
+            if { $crate::__switch! { _ => $output } }
+            else { $crate::__switch! { $($arms)* } }
         }
     };
 }
 
-/// # Examples
-///
-/// ## As a `boolean`
+/// Implementation detail of [`switch`].
+/// Maps `version(...)` to the unstable `cfg(version(...))`, as requested by
+/// `--cfg crossfig_nightly`.
+/// `accessible(...)` has no equivalent predicate, so is always inactive.
+#[cfg(crossfig_nightly)]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __switch_unstable {
+    (
+        version($version:literal) => $output:tt
+        $($arms:tt)*
+    ) => {
+        $crate::__switch! {
+            #[cfg(version($version))] => $output
+            $($arms)*
+        }
+    };
+    (
+        $op:ident($($args:tt)*) => $output:tt
+        $($arms:tt)*
+    ) => {
+        $crate::__switch! { $($arms)* }
+    };
+}
+
+/// Implementation detail of [`switch`].
+/// Treats `version(...)` and `accessible(...)` as inactive, since they require a nightly compiler.
+#[cfg(not(crossfig_nightly))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __switch_unstable {
+    (
+        $op:ident($($args:tt)*) => $output:tt
+        $($arms:tt)*
+    ) => {
+        $crate::__switch! { $($arms)* }
+    };
+}
+
+/// Like [`switch`], but raises a compilation error if no arm is enabled.
 ///
-/// ```
-/// # extern crate crossfig;
-/// assert_eq!(crossfig::disabled!(), false);
-/// ```
+/// Without a wildcard arm, a [`switch`] where no arm matches expands to nothing, which for item
+/// definitions can cause confusing errors much later.
+/// `switch_exhaustive` instead reports every condition it checked.
+/// A wildcard arm can still be provided, in which case it behaves exactly like [`switch`].
 ///
-/// ## As a Conditional Compilation Guard
+/// # Examples
 ///
-/// ```
+/// ```compile_fail
 /// # extern crate crossfig;
-/// crossfig::disabled! {
-///     use a_crate_that_is_not_available::*;
-///     // ...
+/// # use crossfig::{alias, switch_exhaustive};
+/// alias! {
+///     parking_lot: { #[cfg(feature = "parking_lot")] },
+///     std: { #[cfg(feature = "std")] },
 /// }
-/// ```
-///
-/// ## As an `if`-Statement
 ///
-/// ```
-/// # extern crate crossfig;
-/// crossfig::disabled! {
-///     if {
-///         let was_disabled = false;
-///     } else {
-///         let was_disabled = true;
+/// switch_exhaustive! {
+///     parking_lot => {
+///         use parking_lot::Mutex;
+///     }
+///     std => {
+///         use std::sync::Mutex;
 ///     }
 /// }
-///
-/// assert!(was_disabled);
+/// // error: no arm of the switch is enabled, checked:
+/// //   - `parking_lot`
+/// //   - `std`
+/// # fn main() {}
 /// ```
 #[macro_export]
-macro_rules! disabled {
-    () => { false };
-    (if { $($p:tt)* } else { $($n:tt)* }) => { $($n)* };
-    ($($p:tt)*) => {};
+macro_rules! switch_exhaustive {
+    // Allow switch_exhaustive!{{ ... }} to act as an expression in certain contexts
+    ({$($tt:tt)*}) => {
+        { $crate::switch_exhaustive! { $($tt)* } }
+    };
+
+    ($($arms:tt)*) => {
+        $crate::__switch_exhaustive! {
+            @arms []
+            @checked []
+            $($arms)*
+        }
+    };
 }
 
-/// # Examples
-///
-/// ## As a `boolean`
-///
-/// ```
-/// # extern crate crossfig;
-/// assert_eq!(crossfig::enabled!(), true);
-/// ```
-///
-/// ## As a Conditional Compilation Guard
-///
-/// ```
+/// Implementation detail of [`switch_exhaustive`].
+/// Collects the condition of each arm for the error raised if none are enabled.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __switch_exhaustive {
+    // # Exhausted
+    (
+        @arms [$($arms:tt)*]
+        @checked [$($checked:tt)*]
+    ) => {
+        $crate::switch! {
+            $($arms)*
+            _ => {
+                compile_error!(concat!("no arm of the switch is enabled, checked:" $(, "\n  - `", stringify!$checked, "`")*));
+            }
+        }
+    };
+
+    // # Wildcard Branch
+    (
+        @arms [$($arms:tt)*]
+        @checked $checked:tt
+        _ => $($rest:tt)+
+    ) => {
+        $crate::switch! {
+            $($arms)*
+            _ => $($rest)+
+        }
+    };
+
+    // # Arm
+    (
+        @arms [$($arms:tt)*]
+        @checked [$($checked:tt)*]
+        @cond [$($cond:tt)+]
+        => $output:tt
+        $($rest:tt)*
+    ) => {
+        $crate::__switch_exhaustive! {
+            @arms [$($arms)* $($cond)+ => $output]
+            @checked [$($checked)* ($($cond)+)]
+            $($rest)*
+        }
+    };
+    (
+        @arms $arms:tt
+        @checked $checked:tt
+        @cond [$($cond:tt)*]
+        $token:tt $($rest:tt)*
+    ) => {
+        $crate::__switch_exhaustive! {
+            @arms $arms
+            @checked $checked
+            @cond [$($cond)* $token]
+            $($rest)*
+        }
+    };
+    (
+        @arms [$($arms:tt)*]
+        @checked $checked:tt
+        $label:literal: $($rest:tt)+
+    ) => {
+        $crate::__switch_exhaustive! {
+            @arms [$($arms)* $label:]
+            @checked $checked
+            @cond []
+            $($rest)+
+        }
+    };
+    (
+        @arms $arms:tt
+        @checked $checked:tt
+        $($rest:tt)+
+    ) => {
+        $crate::__switch_exhaustive! {
+            @arms $arms
+            @checked $checked
+            @cond []
+            $($rest)+
+        }
+    };
+}
+
+/// Like [`switch`], but also defines a [`Selected`] constant describing the arm which won.
+///
+/// The constant is declared before the arms, and records the position of the selected arm, its
+/// label (see [`switch`]'s preferences), and its condition as written.
+/// This allows a binary to report which configuration it was built with, long after the macro has
+/// expanded.
+/// Since the constant is defined alongside the output of the selected arm, `switch_selected` can
+/// only be used where items are allowed, and each arm's output must be a braced block.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate crossfig;
+/// # use crossfig::{alias, switch_selected};
+/// alias! {
+///     parking_lot: { #[cfg(feature = "parking_lot")] },
+/// }
+///
+/// switch_selected! {
+///     /// The backend used for `Mutex`.
+///     pub const MUTEX_BACKEND;
+///     "parking_lot": parking_lot => {
+///         // use parking_lot::Mutex;
+///     }
+///     "std": #[cfg(feature = "std")] => {
+///         // use std::sync::Mutex;
+///     }
+///     _ => {
+///         // use spin::Mutex;
+///     }
+/// }
+///
+/// # fn main() {
+/// // mutex backend: arm 2, `_`
+/// std::println!("mutex backend: {}", MUTEX_BACKEND);
+/// assert_eq!(MUTEX_BACKEND.index, 2);
+/// assert_eq!(MUTEX_BACKEND.label, None);
+/// assert_eq!(MUTEX_BACKEND.condition, "_");
+/// # }
+/// ```
+///
+/// If no arm is enabled and there is no wildcard, the constant is not defined.
+#[macro_export]
+macro_rules! switch_selected {
+    ($(#[$meta:meta])* $vis:vis const $name:ident; $($arms:tt)*) => {
+        $crate::__switch_selected! {
+            @const [[$(#[$meta])*] [$vis] $name]
+            @index [0]
+            @arms []
+            $($arms)*
+        }
+    };
+}
+
+/// Implementation detail of [`switch_selected`].
+/// Adds the definition of the constant to the output of each arm, counting the arms as it goes.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __switch_selected {
+    // # Definition
+    (
+        @define [$($meta:tt)*] [$vis:vis] $name:ident
+        [$($index:tt)+] [] [$($cond:tt)+]
+    ) => {
+        $($meta)*
+        $vis const $name: $crate::Selected = $crate::Selected::__unlabelled(
+            $($index)+,
+            stringify!($($cond)+),
+        );
+    };
+    (
+        @define [$($meta:tt)*] [$vis:vis] $name:ident
+        [$($index:tt)+] [$label:tt] [$($cond:tt)+]
+    ) => {
+        $($meta)*
+        $vis const $name: $crate::Selected = $crate::Selected::__labelled(
+            $($index)+,
+            $label,
+            stringify!($($cond)+),
+        );
+    };
+
+    // # Exhausted
+    (
+        @const $const:tt
+        @index $index:tt
+        @arms [$($arms:tt)*]
+    ) => {
+        $crate::switch! { $($arms)* }
+    };
+
+    // # Arm
+    (
+        @const [$($const:tt)*]
+        @index [$($index:tt)+]
+        @arms [$($arms:tt)*]
+        @label [$($label:tt)?]
+        @cond [$($cond:tt)+]
+        => { $($output:tt)* }
+        $($rest:tt)*
+    ) => {
+        $crate::__switch_selected! {
+            @const [$($const)*]
+            @index [$($index)+ + 1]
+            @arms [
+                $($arms)*
+                $($label:)? $($cond)+ => {
+                    $crate::__switch_selected! {
+                        @define $($const)* [$($index)+] [$($label)?] [$($cond)+]
+                    }
+                    $($output)*
+                }
+            ]
+            $($rest)*
+        }
+    };
+    (
+        @const $const:tt
+        @index $index:tt
+        @arms $arms:tt
+        @label $label:tt
+        @cond [$($cond:tt)*]
+        $token:tt $($rest:tt)*
+    ) => {
+        $crate::__switch_selected! {
+            @const $const
+            @index $index
+            @arms $arms
+            @label $label
+            @cond [$($cond)* $token]
+            $($rest)*
+        }
+    };
+
+    // # Wildcard Branch
+    // Any arms after the wildcard are passed along for `switch` to reject.
+    (
+        @const [$($const:tt)*]
+        @index [$($index:tt)+]
+        @arms [$($arms:tt)*]
+        _ => { $($output:tt)* }
+        $($rest:tt)*
+    ) => {
+        $crate::__switch_selected! {
+            @const [$($const)*]
+            @index [$($index)+ + 1]
+            @arms [
+                $($arms)*
+                _ => {
+                    $crate::__switch_selected! {
+                        @define $($const)* [$($index)+] [] [_]
+                    }
+                    $($output)*
+                }
+            ]
+            $($rest)*
+        }
+    };
+
+    // # Start of an Arm
+    (
+        @const $const:tt
+        @index $index:tt
+        @arms $arms:tt
+        $label:literal: $($rest:tt)+
+    ) => {
+        $crate::__switch_selected! {
+            @const $const
+            @index $index
+            @arms $arms
+            @label [$label]
+            @cond []
+            $($rest)+
+        }
+    };
+    (
+        @const $const:tt
+        @index $index:tt
+        @arms $arms:tt
+        $($rest:tt)+
+    ) => {
+        $crate::__switch_selected! {
+            @const $const
+            @index $index
+            @arms $arms
+            @label []
+            @cond []
+            $($rest)+
+        }
+    };
+}
+
+/// Selects a single value from a list of arms, using the same conditions as [`switch`].
+///
+/// Each arm is a condition followed by an expression, separated by commas.
+/// Unlike [`switch`], a wildcard `_` arm is _required_, ensuring a value is always produced.
+/// This makes `switch_const` well suited to selecting constants such as buffer sizes or log
+/// levels per platform or feature.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate crossfig;
+/// # use crossfig::{alias, switch_const};
+/// alias! {
+///     embedded: { #[cfg(target_os = "none")] },
+/// }
+///
+/// pub const CHUNK: usize = switch_const! {
+///     embedded => 256,
+///     #[cfg(target_family = "wasm")] => 4096,
+///     _ => 65536,
+/// };
+/// # fn main() {}
+/// ```
+///
+/// Omitting the wildcard arm is an error:
+///
+/// ```compile_fail
+/// # extern crate crossfig;
+/// # use crossfig::switch_const;
+/// pub const CHUNK: usize = switch_const! {
+///     #[cfg(target_family = "wasm")] => 4096,
+/// };
+/// # fn main() {}
+/// ```
+#[macro_export]
+macro_rules! switch_const {
+    ($($arms:tt)*) => {
+        $crate::__switch_const! {
+            @input [$($arms)*]
+            @arms []
+            $($arms)*
+        }
+    };
+}
+
+/// Implementation detail of [`switch_const`].
+/// Converts each arm into a [`switch`] arm, until the wildcard arm is found.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __switch_const {
+    // # Wildcard Branch
+    (
+        @input $input:tt
+        @arms [$($arms:tt)*]
+        _ => $value:expr $(,)?
+    ) => {
+        $crate::switch! {{
+            $($arms)*
+            _ => { $value }
+        }}
+    };
+    // ## Common Mistake: arms after wildcard
+    (
+        @input $input:tt
+        @arms $arms:tt
+        _ => $value:expr,
+        $($rest:tt)+
+    ) => {
+        compile_error!(concat!("patterns after a wildcard are ignored: `", stringify!($($rest)+), "`"))
+    };
+    // ## Common Mistake: no wildcard
+    (
+        @input [$($input:tt)*]
+        @arms $arms:tt
+    ) => {
+        compile_error!(concat!("`switch_const` requires a wildcard `_` arm to fall back on: `", stringify!($($input)*), "`"))
+    };
+
+    // # cfg(...) Integration
+    (
+        @input $input:tt
+        @arms [$($arms:tt)*]
+        #[$($meta:tt)*] => $value:expr
+        $(, $($rest:tt)*)?
+    ) => {
+        $crate::__switch_const! {
+            @input $input
+            @arms [$($arms)* #[$($meta)*] => { $value }]
+            $($($rest)*)?
+        }
+    };
+
+    // # Operations
+    (
+        @input $input:tt
+        @arms [$($arms:tt)*]
+        $op:ident($($cond:tt)*) => $value:expr
+        $(, $($rest:tt)*)?
+    ) => {
+        $crate::__switch_const! {
+            @input $input
+            @arms [$($arms)* $op($($cond)*) => { $value }]
+            $($($rest)*)?
+        }
+    };
+
+    // # Alias Integration
+    (
+        @input $input:tt
+        @arms [$($arms:tt)*]
+        $cond:path => $value:expr
+        $(, $($rest:tt)*)?
+    ) => {
+        $crate::__switch_const! {
+            @input $input
+            @arms [$($arms)* $cond => { $value }]
+            $($($rest)*)?
+        }
+    };
+}
+
+/// # Examples
+///
+/// ## As a `boolean`
+///
+/// ```
+/// # extern crate crossfig;
+/// assert_eq!(crossfig::disabled!(), false);
+/// ```
+///
+/// ## As a Conditional Compilation Guard
+///
+/// ```
+/// # extern crate crossfig;
+/// crossfig::disabled! {
+///     use a_crate_that_is_not_available::*;
+///     // ...
+/// }
+/// ```
+///
+/// ## As an `if`-Statement
+///
+/// ```
+/// # extern crate crossfig;
+/// crossfig::disabled! {
+///     if {
+///         let was_disabled = false;
+///     } else {
+///         let was_disabled = true;
+///     }
+/// }
+///
+/// assert!(was_disabled);
+/// ```
+#[macro_export]
+macro_rules! disabled {
+    () => { false };
+    (if { $($p:tt)* } else { $($n:tt)* }) => { $($n)* };
+    ($($p:tt)*) => {};
+}
+
+/// # Examples
+///
+/// ## As a `boolean`
+///
+/// ```
+/// # extern crate crossfig;
+/// assert_eq!(crossfig::enabled!(), true);
+/// ```
+///
+/// ## As a Conditional Compilation Guard
+///
+/// ```
 /// # extern crate crossfig;
 /// crossfig::enabled! {
 /// # /*
@@ -484,6 +1429,20 @@ macro_rules! enabled {
     ($($p:tt)*) => { $($p)* };
 }
 
+/// Implementation detail of [`alias`].
+/// Behaves as [`enabled`] for aliases whose condition is always active, such as `all()`, and
+/// rejects the arms after such an alias when [`switch`] asks.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __unconditional {
+    () => { true };
+    (if { $krate:tt :: __switch_check! { @unconditional [$($alias:tt)+] [$($rest:tt)+] } } else {}) => {
+        compile_error!(concat!("patterns after `", stringify!($($alias)+), "` are unreachable, as it is always enabled:" $(, " `", stringify!$rest, "`")+));
+    };
+    (if { $($p:tt)* } else { $($n:tt)* }) => { $($p)* };
+    ($($p:tt)*) => { $($p)* };
+}
+
 /// Defines an alias for a particular configuration.
 /// This has two advantages over directly using `#[cfg(...)]`:
 ///
@@ -561,7 +1520,7 @@ macro_rules! enabled {
 ///    # extern crate crossfig;
 ///    # use crossfig::{alias, switch};
 ///    # alias! {
-///    #    std: { all() }
+///    #    std: { #[cfg(all())] }
 ///    # }
 ///    switch! {
 ///        std => {
@@ -575,69 +1534,4587 @@ macro_rules! enabled {
 ///        }
 ///    }
 ///    ```
-#[macro_export]
-macro_rules! alias {
-    // Empty invocations should return nothing
-    () => {};
-
-    // Single arm with no trailing comma
-    (
-        $(#[$p_meta:meta])*
-        $vis:vis $p:ident: { $($cond:tt)+ }
-    ) => {
-        $crate::alias! {
-            $(#[$p_meta])*
-            $vis $p: { $($cond)+ },
-        }
-    };
-
-    // Some number of arms with trailing comma
-    (
-        $(#[$p_meta:meta])*
-        $vis:vis $p:ident: { $($cond:tt)+ },
-        $($rest:tt)*
-    ) => {
-        $crate::switch! {
-            $($cond)+ => {
-                $(#[$p_meta])*
-                #[doc(inline)]
-                ///
-                #[doc = concat!("This macro passes the provided code because `", stringify!($($cond)+), "` is currently active.")]
-                $vis use $crate::enabled as $p;
-            }
-            _ => {
-                $(#[$p_meta])*
-                #[doc(inline)]
-                ///
-                #[doc = concat!("This macro suppresses the provided code because `", stringify!($($cond)+), "` is _not_ currently active.")]
-                $vis use $crate::disabled as $p;
-            }
-        }
+///
+/// # Deprecation
+///
+/// An alias can be marked `#[deprecated]`, raising a deprecation warning wherever it is invoked or
+/// used in a [`switch`] arm, while still evaluating exactly as before.
+/// This is useful when renaming an exported alias.
+///
+/// ```compile_fail
+/// # #![deny(deprecated)]
+/// # extern crate crossfig;
+/// # use crossfig::alias;
+/// alias! {
+///     pub parallel: { #[cfg(feature = "parallel")] },
+///     #[deprecated(note = "renamed to `parallel`")]
+///     pub threads: { parallel },
+/// }
+///
+/// if threads!() {
+///     // warning: use of deprecated macro `threads`: renamed to `parallel`
+/// }
+/// ```
+///
+/// Since `#[deprecated]` only applies to macro definitions, a deprecated alias is defined as its own
+/// macro rather than re-exported.
+/// As such, a `pub` deprecated alias is `#[macro_export]`ed from the crate root, and a private
+/// deprecated alias is only available textually after its definition, like any other
+/// `macro_rules` macro.
+/// A deprecated alias with a restricted visibility, such as `pub(crate)`, is also re-exported with
+/// that visibility, so it can still be used by path.
+/// Re-exporting a `macro_rules` macro requires the crate defining the alias to use the 2018 edition
+/// or later.
+///
+/// ```edition2018
+/// # extern crate crossfig;
+/// mod cfg {
+///     crossfig::alias! {
+///         pub(crate) parallel: { #[cfg(feature = "parallel")] },
+///         #[deprecated(note = "renamed to `parallel`")]
+///         pub(crate) threads: { parallel },
+///     }
+/// }
+///
+/// # fn main() {
+/// if cfg::threads!() {
+///     // warning: use of deprecated macro `cfg::threads`: renamed to `parallel`
+/// }
+/// # }
+/// ```
+///
+/// Since an exported macro can't be re-exported from a module of its own crate, a `pub` deprecated
+/// alias must be defined at the crate root, rather than silently moving it there.
+/// Defining one anywhere else raises a type error naming the alias.
+///
+/// ```compile_fail,E0308
+/// # extern crate crossfig;
+/// pub mod cfg {
+///     crossfig::alias! {
+///         #[deprecated(note = "renamed to `parallel`")]
+///         pub threads: { #[cfg(feature = "parallel")] },
+///     }
+///     // error[E0308]: mismatched types
+///     // expected `threads`, found `PublicAliasMustBeDefinedAtTheCrateRoot`
+/// }
+/// # fn main() {}
+/// ```
+///
+/// # Call-Site Evaluation
+///
+/// Sometimes an exported predicate should instead be evaluated in the _consuming_ crate, such as
+/// whether the consumer is compiling its own tests.
+/// An alias marked `#[call_site]` expands its condition through [`switch`] wherever it is used,
+/// and supports the same 4 forms of usage.
+///
+/// ```
+/// # extern crate crossfig;
+/// # use crossfig::alias;
+/// alias! {
+///     /// Indicates whether the _calling_ crate is compiling its tests.
+///     #[call_site]
+///     pub test: { #[cfg(test)] },
+/// }
+///
+/// # fn main() {
+/// assert!(!test!());
+/// # }
+/// ```
+///
+/// Since the condition is expanded at the call site, any aliases it refers to are also resolved at
+/// the call site, and should be referred to by paths available to consumers.
+/// Like deprecated aliases, a `pub` call-site alias is `#[macro_export]`ed from the crate root, so
+/// must be defined there, and one with a restricted visibility is re-exported with it.
+///
+/// # `#[macro_use]`
+///
+/// Crates using `#[macro_use] extern crate foo;` can only see macros exported from the root of
+/// `foo`, and share a single namespace for those macros across all dependencies, where a later
+/// `#[macro_use]` silently shadows an earlier one.
+/// An alias marked `#[macro_export(name)]` is additionally exported from the crate root as `name`,
+/// wherever it is defined.
+/// Prefixing `name` with the crate name avoids clashing with other crates exporting the same alias.
+///
+/// ```
+/// # extern crate crossfig;
+/// // In the `foo` crate:
+/// pub mod cfg {
+///     crossfig::alias! {
+///         /// Indicates whether the `std` feature is enabled.
+///         #[macro_export(foo_std)]
+///         pub std: { #[cfg(feature = "std")] },
+///     }
+/// }
+///
+/// // In a consumer's crate, after `#[macro_use] extern crate foo;`:
+/// # fn main() {
+/// if foo_std!() {
+///     // `foo`'s `std` feature has been enabled!
+/// }
+/// # }
+/// ```
+///
+/// # Optional Dependencies
+///
+/// A path to an alias must resolve wherever it's evaluated, which isn't the case for an optional
+/// dependency that hasn't been enabled.
+/// Instead, an alias of an optional dependency can be named with `dep(feature = "...", ...)`,
+/// which first checks the feature enabling that dependency, and only then evaluates its alias.
+///
+/// ```
+/// # extern crate crossfig;
+/// # use crossfig::alias;
+/// # /*
+/// #[cfg(feature = "foo")]
+/// extern crate foo;
+/// # */
+///
+/// alias! {
+///     /// Indicates whether `foo` is present, with its `std` feature enabled.
+///     pub foo_std: { dep(feature = "foo", foo::std) },
+/// }
+/// # fn main() {
+/// # assert!(!foo_std!());
+/// # }
+/// ```
+///
+/// # Modules
+///
+/// Aliases can be grouped into a module with `mod name { ... }`, giving a crate a consistent
+/// surface such as `foo::cfg::std!()` without writing the module by hand.
+/// Alongside each alias, the module defines a `bool` constant of the same name and visibility,
+/// and a public constant `MEMBERS` listing every alias in the module as a [`Member`], in order.
+///
+/// ```
+/// # extern crate crossfig;
+/// # use crossfig::alias;
+/// alias! {
+///     /// The features of this crate.
+///     pub mod cfg {
+///         /// Indicates whether the `std` feature is enabled.
+///         pub std: { #[cfg(feature = "std")] },
+///         /// Indicates whether the `std` feature is _not_ enabled.
+///         pub no_std: { not(std) },
+///     }
+/// }
+///
+/// # fn main() {
+/// assert_eq!(cfg::std, cfg::std!());
+/// assert_eq!(cfg::MEMBERS.len(), 2);
+/// assert_eq!(cfg::MEMBERS[1].name, "no_std");
+/// assert_eq!(cfg::MEMBERS[1].enabled, cfg::no_std);
+/// # }
+/// ```
+///
+/// Conditions are evaluated within the module, so aliases outside it must be named with `super::`.
+/// As with any alias defined as its own macro, aliases marked `#[deprecated]` or `#[call_site]`
+/// can't be `pub` within a module, since they would be exported from the crate root instead.
+/// With a restricted visibility such as `pub(crate)`, they are re-exported from the module as
+/// usual, which requires the 2018 edition or later.
+///
+/// ```edition2018
+/// # extern crate crossfig;
+/// # use crossfig::alias;
+/// alias! {
+///     pub mod cfg {
+///         pub parallel: { #[cfg(feature = "parallel")] },
+///         #[deprecated(note = "renamed to `parallel`")]
+///         pub(crate) threads: { parallel },
+///     }
+/// }
+///
+/// # fn main() {
+/// # #[allow(deprecated)]
+/// assert_eq!(cfg::threads!(), cfg::parallel);
+/// # }
+/// ```
+///
+/// ```compile_fail,E0308
+/// # extern crate crossfig;
+/// # use crossfig::alias;
+/// alias! {
+///     pub mod cfg {
+///         pub parallel: { #[cfg(feature = "parallel")] },
+///         #[deprecated(note = "renamed to `parallel`")]
+///         pub threads: { parallel },
+///     }
+/// }
+/// // error[E0308]: mismatched types
+/// // expected `threads`, found `PublicAliasMustBeDefinedAtTheCrateRoot`
+/// # fn main() {}
+/// ```
+///
+/// A module marked `#[for_each(name)]` additionally defines a macro `name`, which takes the name
+/// of another macro and invokes it once for each alias in the module, in order, as
+/// `callback! { alias, enabled, condition }`.
+/// `enabled` is the literal `true` or `false`, and `condition` is a `stringify!(...)` expression
+/// evaluating to the condition of the alias as a `&'static str`, so should be matched as `expr`
+/// rather than `literal`.
+/// This allows test cases, registries or `Display` implementations to be generated from a single
+/// list of aliases.
+///
+/// ```
+/// # extern crate crossfig;
+/// # use crossfig::alias;
+/// alias! {
+///     #[for_each(cfg_for_each)]
+///     pub mod cfg {
+///         pub std: { #[cfg(feature = "std")] },
+///         pub no_std: { not(std) },
+///     }
+/// }
+///
+/// macro_rules! report {
+///     ($alias:ident, $enabled:literal, $condition:expr) => {
+///         std::println!("{}: {} (`{}`)", stringify!($alias), $enabled, $condition);
+///     };
+/// }
+///
+/// # fn main() {
+/// // std: false (`#[cfg(feature = "std")]`)
+/// // no_std: true (`not(std)`)
+/// cfg_for_each!(report);
+/// # }
+/// ```
+///
+/// Macros defined by other macros can't be re-exported by path, so rather than being available as
+/// `cfg::for_each!`, the macro is named by the attribute, is visible after the module within the
+/// defining crate, and is exported from the crate root if the module is public.
+/// Similarly, a macro can't turn the tokens of a condition into a string literal, only into a
+/// `stringify!(...)` expression, which is why `condition` isn't a literal.
+/// Like the other constants in the module, the states are those of the aliases themselves, so are
+/// evaluated at the _definition site_.
+///
+/// # Type-Level Booleans
+///
+/// An alias marked `#[type_bool(name)]` additionally defines a type alias `name` for either
+/// [`True`] or [`False`], with the same visibility as the alias.
+/// This allows generic code to select types and implementations through the type system, using
+/// [`Bool`] and [`If`].
+///
+/// ```
+/// # extern crate crossfig;
+/// # use crossfig::{alias, Bool, If};
+/// alias! {
+///     /// Indicates whether the `std` feature is enabled.
+///     #[type_bool(std_ty)]
+///     pub std: { #[cfg(feature = "std")] },
+/// }
+///
+/// # struct Heap; struct Stack;
+/// type Storage = If<std_ty, Heap, Stack>;
+///
+/// # fn main() {
+/// assert_eq!(std_ty::VALUE, std!());
+/// # }
+/// ```
+///
+/// Like a type, the type-level boolean is always evaluated at the _definition site_, even for an
+/// alias marked `#[call_site]`.
+///
+/// # Marker Traits
+///
+/// An alias marked `#[marker_trait(Name)]` additionally defines a trait `Name`, with the same
+/// visibility as the alias, which is implemented for all types if and only if the alias is enabled.
+/// APIs bound on this trait always exist, but can only be called while the alias is enabled.
+/// Any further arguments are passed to `#[diagnostic::on_unimplemented(...)]`, customising the
+/// error raised when the alias is disabled.
+///
+/// ```compile_fail
+/// # extern crate crossfig;
+/// # use crossfig::alias;
+/// alias! {
+///     #[marker_trait(
+///         Std,
+///         message = "this requires `foo/std`",
+///         note = "enable the `std` feature of `foo` in your Cargo.toml",
+///     )]
+///     pub std: { #[cfg(feature = "std")] },
+/// }
+///
+/// pub fn print<T: Std + core::fmt::Debug>(value: T) {
+///     // ...
+/// }
+///
+/// # fn main() {
+/// print(123);
+/// // error[E0277]: this requires `foo/std`
+/// # }
+/// ```
+///
+/// Note that `#[diagnostic::on_unimplemented(...)]` requires Rust 1.78, and is unavailable in crates
+/// using `#![no_implicit_prelude]`.
+/// As such, it is only emitted when building with `--cfg crossfig_diagnostic`, falling back to the
+/// standard error for an unsatisfied bound on the marker trait otherwise.
+/// The marker trait can't be implemented manually, so it can't be used to bypass the alias.
+///
+/// ```compile_fail
+/// # extern crate crossfig;
+/// # use crossfig::alias;
+/// alias! {
+///     #[marker_trait(Never)]
+///     pub never: { #[cfg(any())] },
+/// }
+///
+/// struct Mine;
+///
+/// impl Never for Mine {}
+/// // error[E0277]: the trait bound `Mine: crossfig::__Unimplemented` is not satisfied
+/// # fn main() {}
+/// ```
+///
+/// Like a type-level boolean, the marker trait is always evaluated at the _definition site_.
+///
+/// # Profiles
+///
+/// An alias combining several others with `all(...)` can be marked `#[profile(NAME)]`, additionally
+/// defining a constant `NAME` with the same visibility as the alias.
+/// It lists each [`Member`] of the profile in order, along with whether it is currently active, so
+/// reports can show exactly which members are missing.
+/// The documentation of the constant lists the state of each member too.
+///
+/// ```
+/// # extern crate crossfig;
+/// # use crossfig::alias;
+/// alias! {
+///     pub std: { #[cfg(feature = "std")] },
+///     pub alloc: { #[cfg(feature = "alloc")] },
+///     pub bare_metal: { #[cfg(target_os = "none")] },
+///
+///     /// Indicates a target without an operating system or allocator.
+///     #[profile(EMBEDDED)]
+///     pub embedded: { all(not(std), not(alloc), bare_metal) },
+/// }
+///
+/// # fn main() {
+/// for member in EMBEDDED.iter().filter(|member| !member.enabled) {
+///     std::println!("`embedded` is inactive, as `{}` is not", member.name);
+/// }
+/// assert_eq!(embedded!(), EMBEDDED.iter().all(|member| member.enabled));
+/// # }
+/// ```
+///
+/// Like a type-level boolean, the members are always evaluated at the _definition site_.
+///
+/// # Overrides
+///
+/// An alias marked `#[overridable("key")]` can be forced on or off without changing any features,
+/// by building with `--cfg crossfig_force_on="key"` or `--cfg crossfig_force_off="key"`
+/// respectively, such as to test a fallback or to work around a broken backend.
+/// If both are provided, `crossfig_force_off` takes precedence.
+/// The documentation of the alias notes when an override decided its state, as does the row of any
+/// [`alias_table`] including it.
+///
+/// ```
+/// # extern crate crossfig;
+/// # use crossfig::alias;
+/// // In the `foo` crate:
+/// alias! {
+///     #[overridable("foo::parallel")]
+///     pub parallel: { #[cfg(feature = "parallel")] },
+/// }
+/// # fn main() {}
+/// ```
+///
+/// Overrides are opt-in, since the key must be a string literal, and macros such as `stringify!` or
+/// `module_path!` aren't expanded within `cfg`, so no key can be derived from the alias itself.
+/// The override is evaluated in the crate defining the alias, which should declare both
+/// configuration options to avoid `unexpected_cfgs` warnings:
+///
+/// ```toml
+/// [lints.rust.unexpected_cfgs]
+/// level = "warn"
+/// check-cfg = ['cfg(crossfig_force_on, values(any()))', 'cfg(crossfig_force_off, values(any()))']
+/// ```
+///
+/// Type-level booleans and marker traits defined by an overridable alias respect the override too.
+#[macro_export]
+macro_rules! alias {
+    // Empty invocations should return nothing
+    () => {};
+
+    // Modules, optionally followed by a comma
+    // Public visibility is matched separately so it can be inspected by `__alias_mod`
+    (
+        $(#[$($m_meta:tt)*])*
+        $vis:vis mod $name:ident { $($aliases:tt)* },
+        $($rest:tt)*
+    ) => {
+        $crate::alias! {
+            $(#[$($m_meta)*])*
+            $vis mod $name { $($aliases)* }
+            $($rest)*
+        }
+    };
+    (
+        $(#[$($m_meta:tt)*])*
+        pub mod $name:ident { $($aliases:tt)* }
+        $($rest:tt)*
+    ) => {
+        $crate::__alias_mod! {
+            @attrs []
+            @for_each []
+            $(#[$($m_meta)*])*
+            @vis [pub] mod $name { $($aliases)* }
+        }
+
+        $crate::alias! {
+            $($rest)*
+        }
+    };
+    (
+        $(#[$($m_meta:tt)*])*
+        $vis:vis mod $name:ident { $($aliases:tt)* }
+        $($rest:tt)*
+    ) => {
+        $crate::__alias_mod! {
+            @attrs []
+            @for_each []
+            $(#[$($m_meta)*])*
+            @vis [$vis] mod $name { $($aliases)* }
+        }
+
+        $crate::alias! {
+            $($rest)*
+        }
+    };
+
+    // Single arm with no trailing comma
+    (
+        $(#[$($p_meta:tt)*])*
+        pub $p:ident: { $($cond:tt)+ }
+    ) => {
+        $crate::alias! {
+            $(#[$($p_meta)*])*
+            pub $p: { $($cond)+ },
+        }
+    };
+    (
+        $(#[$($p_meta:tt)*])*
+        pub($($restriction:tt)+) $p:ident: { $($cond:tt)+ }
+    ) => {
+        $crate::alias! {
+            $(#[$($p_meta)*])*
+            pub($($restriction)+) $p: { $($cond)+ },
+        }
+    };
+    (
+        $(#[$($p_meta:tt)*])*
+        $vis:vis $p:ident: { $($cond:tt)+ }
+    ) => {
+        $crate::alias! {
+            $(#[$($p_meta)*])*
+            $vis $p: { $($cond)+ },
+        }
+    };
+
+    // Some number of arms with trailing comma
+    // Public and restricted visibilities are matched separately so they can be inspected by `__alias`
+    (
+        $(#[$($p_meta:tt)*])*
+        pub $p:ident: { $($cond:tt)+ },
+        $($rest:tt)*
+    ) => {
+        $crate::__alias! {
+            @override []
+            @scan []
+            $(#[$($p_meta)*])*
+            @vis [pub] $p: { $($cond)+ }
+        }
+
+        $crate::alias! {
+            $($rest)*
+        }
+    };
+    (
+        $(#[$($p_meta:tt)*])*
+        pub($($restriction:tt)+) $p:ident: { $($cond:tt)+ },
+        $($rest:tt)*
+    ) => {
+        $crate::__alias! {
+            @override []
+            @scan []
+            $(#[$($p_meta)*])*
+            @vis [pub($($restriction)+)] $p: { $($cond)+ }
+        }
+
+        $crate::alias! {
+            $($rest)*
+        }
+    };
+    (
+        $(#[$($p_meta:tt)*])*
+        $vis:vis $p:ident: { $($cond:tt)+ },
+        $($rest:tt)*
+    ) => {
+        $crate::__alias! {
+            @override []
+            @scan []
+            $(#[$($p_meta)*])*
+            @vis [$vis] $p: { $($cond)+ }
+        }
+
+        $crate::alias! {
+            $($rest)*
+        }
+    };
+}
+
+/// Implementation detail of [`alias`].
+/// Finds any `#[overridable(...)]` attribute, then separates any `#[deprecated]`,
+/// `#[macro_export(...)]` and `#[call_site]` attributes from the other attributes of a single
+/// alias, then defines that alias.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __alias {
+    // # Overrides
+    // Found before any other attribute, so that every definition derived from the alias respects it.
+    (
+        @override []
+        @scan [$($seen:tt)*]
+        #[overridable($key:tt)]
+        $($rest:tt)*
+    ) => {
+        $crate::__alias! {
+            @override [$key]
+            @scan [$($seen)*]
+            $($rest)*
+        }
+    };
+    (
+        @override $override:tt
+        @scan [$($seen:tt)*]
+        #[$($p_meta:tt)*]
+        $($rest:tt)*
+    ) => {
+        $crate::__alias! {
+            @override $override
+            @scan [$($seen)* #[$($p_meta)*]]
+            $($rest)*
+        }
+    };
+    (
+        @override $override:tt
+        @scan [$($seen:tt)*]
+        @vis $($rest:tt)*
+    ) => {
+        $crate::__alias! {
+            @attrs []
+            @deprecated []
+            @export []
+            @kind []
+            @override $override
+            $($seen)*
+            @vis $($rest)*
+        }
+    };
+    (
+        @switch []
+        [$($cond:tt)+]
+        { $($enabled:tt)* }
+        { $($disabled:tt)* }
+    ) => {
+        $crate::switch! {
+            $($cond)+ => { $($enabled)* }
+            _ => { $($disabled)* }
+        }
+    };
+    (
+        @switch [$key:tt]
+        [$($cond:tt)+]
+        { $($enabled:tt)* }
+        { $($disabled:tt)* }
+    ) => {
+        $crate::switch! {
+            #[cfg(crossfig_force_off = $key)] => { $($disabled)* }
+            #[cfg(crossfig_force_on = $key)] => { $($enabled)* }
+            $($cond)+ => { $($enabled)* }
+            _ => { $($disabled)* }
+        }
+    };
+
+    // # Attributes
+    // ## Deprecation
+    (
+        @attrs $attrs:tt
+        @deprecated []
+        @export $export:tt
+        @kind $kind:tt
+        @override $override:tt
+        #[deprecated $($note:tt)*]
+        $($rest:tt)*
+    ) => {
+        $crate::__alias! {
+            @attrs $attrs
+            @deprecated [#[deprecated $($note)*]]
+            @export $export
+            @kind $kind
+            @override $override
+            $($rest)*
+        }
+    };
+    // ## Legacy Export
+    (
+        @attrs $attrs:tt
+        @deprecated $deprecated:tt
+        @export []
+        @kind $kind:tt
+        @override $override:tt
+        #[macro_export($export:ident)]
+        $($rest:tt)*
+    ) => {
+        $crate::__alias! {
+            @attrs $attrs
+            @deprecated $deprecated
+            @export [$export]
+            @kind $kind
+            @override $override
+            $($rest)*
+        }
+    };
+    // ## Common Mistake: exporting without a distinct name
+    (
+        @attrs $attrs:tt
+        @deprecated $deprecated:tt
+        @export $export:tt
+        @kind $kind:tt
+        @override $override:tt
+        #[macro_export]
+        $($rest:tt)*
+    ) => {
+        compile_error!("aliases exported for `#[macro_use]` must be given a distinct name, e.g., `#[macro_export(foo_std)]`");
+    };
+    // ## Call-Site Evaluation
+    (
+        @attrs $attrs:tt
+        @deprecated $deprecated:tt
+        @export $export:tt
+        @kind []
+        @override $override:tt
+        #[call_site]
+        $($rest:tt)*
+    ) => {
+        $crate::__alias! {
+            @attrs $attrs
+            @deprecated $deprecated
+            @export $export
+            @kind [call_site]
+            @override $override
+            $($rest)*
+        }
+    };
+    // ## Type-Level Boolean
+    (
+        @attrs $attrs:tt
+        @deprecated $deprecated:tt
+        @export $export:tt
+        @kind $kind:tt
+        @override $override:tt
+        #[type_bool($ty:ident)]
+        $(#[$($p_meta:tt)*])*
+        @vis $vis:tt $p:ident: { $($cond:tt)+ }
+    ) => {
+        $crate::__alias! {
+            @switch $override [$($cond)+]
+            {
+                $crate::__alias! {
+                    @type_bool $vis $ty => True
+                    #[doc = concat!("This type is `True` because `", stringify!($($cond)+), "` is currently active.")]
+                }
+            }
+            {
+                $crate::__alias! {
+                    @type_bool $vis $ty => False
+                    #[doc = concat!("This type is `False` because `", stringify!($($cond)+), "` is _not_ currently active.")]
+                }
+            }
+        }
+
+        $crate::__alias! {
+            @attrs $attrs
+            @deprecated $deprecated
+            @export $export
+            @kind $kind
+            @override $override
+            $(#[$($p_meta)*])*
+            @vis $vis $p: { $($cond)+ }
+        }
+    };
+    // ## Marker Trait
+    (
+        @attrs $attrs:tt
+        @deprecated $deprecated:tt
+        @export $export:tt
+        @kind $kind:tt
+        @override $override:tt
+        #[marker_trait($name:ident $(, $($diagnostic:tt)*)?)]
+        $(#[$($p_meta:tt)*])*
+        @vis $vis:tt $p:ident: { $($cond:tt)+ }
+    ) => {
+        $crate::__alias! {
+            @switch $override [$($cond)+]
+            {
+                $crate::__alias! {
+                    @marker_trait $vis $name [$($($diagnostic)*)?] => enabled
+                    #[doc = concat!("This trait is implemented for all types because `", stringify!($($cond)+), "` is currently active.")]
+                }
+            }
+            {
+                $crate::__alias! {
+                    @marker_trait $vis $name [$($($diagnostic)*)?] => disabled
+                    #[doc = concat!("This trait is not implemented for any type because `", stringify!($($cond)+), "` is _not_ currently active.")]
+                }
+            }
+        }
+
+        $crate::__alias! {
+            @attrs $attrs
+            @deprecated $deprecated
+            @export $export
+            @kind $kind
+            @override $override
+            $(#[$($p_meta)*])*
+            @vis $vis $p: { $($cond)+ }
+        }
+    };
+    // ## Profile
+    (
+        @attrs $attrs:tt
+        @deprecated $deprecated:tt
+        @export $export:tt
+        @kind $kind:tt
+        @override $override:tt
+        #[profile($name:ident)]
+        $(#[$($p_meta:tt)*])*
+        @vis $vis:tt $p:ident: { all($($members:tt)*) }
+    ) => {
+        $crate::__alias! {
+            @profile $vis $name $p
+            @split [] []
+            $($members)*
+        }
+
+        $crate::__alias! {
+            @attrs $attrs
+            @deprecated $deprecated
+            @export $export
+            @kind $kind
+            @override $override
+            $(#[$($p_meta)*])*
+            @vis $vis $p: { all($($members)*) }
+        }
+    };
+    // ## Common Mistake: profile without `all(...)`
+    (
+        @attrs $attrs:tt
+        @deprecated $deprecated:tt
+        @export $export:tt
+        @kind $kind:tt
+        @override $override:tt
+        #[profile($($name:tt)*)]
+        $($rest:tt)*
+    ) => {
+        compile_error!("a profile must be given a name for its members, e.g., `#[profile(MEMBERS)]`, and a condition of the form `all(...)`");
+    };
+    // ## Other
+    (
+        @attrs [$($attrs:tt)*]
+        @deprecated $deprecated:tt
+        @export $export:tt
+        @kind $kind:tt
+        @override $override:tt
+        #[$($p_meta:tt)*]
+        $($rest:tt)*
+    ) => {
+        $crate::__alias! {
+            @attrs [$($attrs)* #[$($p_meta)*]]
+            @deprecated $deprecated
+            @export $export
+            @kind $kind
+            @override $override
+            $($rest)*
+        }
+    };
+
+    // # Definition
+    // ## Evaluated at the call site
+    (
+        @attrs [$($attrs:tt)*]
+        @deprecated [$($deprecated:tt)*]
+        @export $export:tt
+        @kind [call_site]
+        @override [$key:tt]
+        @vis $vis:tt $p:ident: { $($cond:tt)+ }
+    ) => {
+        $crate::__alias! {
+            @attrs [$($attrs)*]
+            @deprecated [$($deprecated)*]
+            @export $export
+            @kind [call_site]
+            @override []
+            @vis $vis $p: {
+                all(
+                    not(#[cfg(crossfig_force_off = $key)]),
+                    any(#[cfg(crossfig_force_on = $key)], $($cond)+),
+                )
+            }
+        }
+    };
+    (
+        @attrs [$($attrs:tt)*]
+        @deprecated [$($deprecated:tt)*]
+        @export $export:tt
+        @kind [call_site]
+        @override []
+        @vis $vis:tt $p:ident: { $($cond:tt)+ }
+    ) => {
+        $crate::__alias_macro! {
+            ($)
+            [
+                $($attrs)*
+                $($deprecated)*
+                ///
+                #[doc = concat!("This macro passes the provided code if `", stringify!($($cond)+), "` is active where it is _used_.")]
+                ///
+                /// This alias is evaluated at the _call site_, in the context of the consuming crate.
+            ]
+            $vis $p => { $($cond)+ }
+        }
+
+        $crate::__alias! {
+            @export $export => { $($cond)+ }
+            @attrs [
+                $($attrs)*
+                $($deprecated)*
+                ///
+                #[doc = concat!("This macro passes the provided code if `", stringify!($($cond)+), "` is active where it is _used_.")]
+                ///
+                /// This alias is evaluated at the _call site_, in the context of the consuming crate.
+            ]
+        }
+    };
+    // ## Evaluated at the definition site
+    (
+        @attrs [$($attrs:tt)*]
+        @deprecated $deprecated:tt
+        @export $export:tt
+        @kind []
+        @override [$key:tt]
+        @vis $vis:tt $p:ident: { $($cond:tt)+ }
+    ) => {
+        $crate::switch! {
+            #[cfg(crossfig_force_off = $key)] => {
+                $crate::__alias! {
+                    @define disabled
+                    @attrs [$($attrs)*]
+                    @doc [
+                        ///
+                        #[doc = concat!("This macro suppresses the provided code because it was forced off by `--cfg crossfig_force_off=", stringify!($key), "`.")]
+                        ///
+                        /// This alias is evaluated at the _definition site_, in the context of the defining crate.
+                    ]
+                    @deprecated $deprecated
+                    @export $export
+                    @vis $vis $p
+                }
+            }
+            #[cfg(crossfig_force_on = $key)] => {
+                $crate::__alias! {
+                    @define enabled
+                    @attrs [$($attrs)*]
+                    @doc [
+                        ///
+                        #[doc = concat!("This macro passes the provided code because it was forced on by `--cfg crossfig_force_on=", stringify!($key), "`.")]
+                        ///
+                        /// This alias is evaluated at the _definition site_, in the context of the defining crate.
+                    ]
+                    @deprecated $deprecated
+                    @export $export
+                    @vis $vis $p
+                }
+            }
+            $($cond)+ => {
+                $crate::__alias! {
+                    @define enabled
+                    @attrs [$($attrs)*]
+                    @doc [
+                        ///
+                        #[doc = concat!("This macro passes the provided code because `", stringify!($($cond)+), "` is currently active.")]
+                        ///
+                        /// This alias is evaluated at the _definition site_, in the context of the defining crate.
+                    ]
+                    @deprecated $deprecated
+                    @export $export
+                    @vis $vis $p
+                }
+            }
+            _ => {
+                $crate::__alias! {
+                    @define disabled
+                    @attrs [$($attrs)*]
+                    @doc [
+                        ///
+                        #[doc = concat!("This macro suppresses the provided code because `", stringify!($($cond)+), "` is _not_ currently active.")]
+                        ///
+                        /// This alias is evaluated at the _definition site_, in the context of the defining crate.
+                    ]
+                    @deprecated $deprecated
+                    @export $export
+                    @vis $vis $p
+                }
+            }
+        }
+    };
+    (
+        @attrs [$($attrs:tt)*]
+        @deprecated $deprecated:tt
+        @export $export:tt
+        @kind []
+        @override []
+        @vis $vis:tt $p:ident: { $($cond:tt)+ }
+    ) => {
+        $crate::switch! {
+            $($cond)+ => {
+                $crate::__alias! {
+                    @define [$($cond)+]
+                    @attrs [$($attrs)*]
+                    @doc [
+                        ///
+                        #[doc = concat!("This macro passes the provided code because `", stringify!($($cond)+), "` is currently active.")]
+                        ///
+                        /// This alias is evaluated at the _definition site_, in the context of the defining crate.
+                    ]
+                    @deprecated $deprecated
+                    @export $export
+                    @vis $vis $p
+                }
+            }
+            _ => {
+                $crate::__alias! {
+                    @define disabled
+                    @attrs [$($attrs)*]
+                    @doc [
+                        ///
+                        #[doc = concat!("This macro suppresses the provided code because `", stringify!($($cond)+), "` is _not_ currently active.")]
+                        ///
+                        /// This alias is evaluated at the _definition site_, in the context of the defining crate.
+                    ]
+                    @deprecated $deprecated
+                    @export $export
+                    @vis $vis $p
+                }
+            }
+        }
+    };
+    // ### Marking aliases which are always enabled
+    // Only checked without an override, since that could force the alias off.
+    (@define [all()] $($rest:tt)*) => {
+        $crate::__alias! { @define __unconditional $($rest)* }
+    };
+    (@define [enabled] $($rest:tt)*) => {
+        $crate::__alias! { @define __unconditional $($rest)* }
+    };
+    (@define [crossfig::enabled] $($rest:tt)*) => {
+        $crate::__alias! { @define __unconditional $($rest)* }
+    };
+    (@define [::crossfig::enabled] $($rest:tt)*) => {
+        $crate::__alias! { @define __unconditional $($rest)* }
+    };
+    (@define [$($cond:tt)+] $($rest:tt)*) => {
+        $crate::__alias! { @define enabled $($rest)* }
+    };
+    // ### As a re-export of `enabled` or `disabled`
+    (
+        @define $target:ident
+        @attrs [$($attrs:tt)*]
+        @doc [$($doc:tt)*]
+        @deprecated []
+        @export $export:tt
+        @vis [$($vis:tt)*] $p:ident
+    ) => {
+        $($attrs)*
+        #[doc(inline)]
+        $($doc)*
+        $($vis)* use $crate::$target as $p;
+
+        $crate::__alias! {
+            @export $export => $target
+            @attrs [$($attrs)* $($doc)*]
+        }
+    };
+    // ### As a deprecated macro
+    (
+        @define $target:ident
+        @attrs [$($attrs:tt)*]
+        @doc [$($doc:tt)*]
+        @deprecated [$($deprecated:tt)+]
+        @export $export:tt
+        @vis $vis:tt $p:ident
+    ) => {
+        $crate::__alias_macro! {
+            ($)
+            [$($attrs)* $($deprecated)+ $($doc)*]
+            $vis $p => $target
+        }
+
+        $crate::__alias! {
+            @export $export => $target
+            @attrs [$($attrs)* $($deprecated)+ $($doc)*]
+        }
+    };
+
+    // # Profile
+    // ## Splitting the members at each comma
+    (
+        @profile $vis:tt $name:ident $p:ident
+        @split [$($members:tt)*] [$($member:tt)+]
+        , $($rest:tt)*
+    ) => {
+        $crate::__alias! {
+            @profile $vis $name $p
+            @split [$($members)* [$($member)+]] []
+            $($rest)*
+        }
+    };
+    (
+        @profile $vis:tt $name:ident $p:ident
+        @split $members:tt [$($member:tt)*]
+        $token:tt $($rest:tt)*
+    ) => {
+        $crate::__alias! {
+            @profile $vis $name $p
+            @split $members [$($member)* $token]
+            $($rest)*
+        }
+    };
+    (
+        @profile $vis:tt $name:ident $p:ident
+        @split [$($members:tt)*] [$($member:tt)*]
+    ) => {
+        $crate::__alias! {
+            @profile $vis $name $p
+            @entries []
+            @docs []
+            $($members)* [$($member)*]
+        }
+    };
+    // ## Evaluating each member in turn
+    (
+        @profile $vis:tt $name:ident $p:ident
+        @entries [$($entries:tt)*]
+        @docs [$($docs:tt)*]
+        [$($member:tt)+]
+        $($rest:tt)*
+    ) => {
+        $crate::switch! {
+            $($member)+ => {
+                $crate::__alias! {
+                    @profile $vis $name $p
+                    @entries [
+                        $($entries)*
+                        $crate::Member {
+                            name: stringify!($($member)+),
+                            condition: stringify!($($member)+),
+                            enabled: true,
+                        },
+                    ]
+                    @docs [
+                        $($docs)*
+                        #[doc = concat!("- `", stringify!($($member)+), "` is active.")]
+                    ]
+                    $($rest)*
+                }
+            }
+            _ => {
+                $crate::__alias! {
+                    @profile $vis $name $p
+                    @entries [
+                        $($entries)*
+                        $crate::Member {
+                            name: stringify!($($member)+),
+                            condition: stringify!($($member)+),
+                            enabled: false,
+                        },
+                    ]
+                    @docs [
+                        $($docs)*
+                        #[doc = concat!("- `", stringify!($($member)+), "` is _not_ active.")]
+                    ]
+                    $($rest)*
+                }
+            }
+        }
+    };
+    // A trailing comma leaves an empty member
+    (
+        @profile $vis:tt $name:ident $p:ident
+        @entries $entries:tt
+        @docs $docs:tt
+        []
+    ) => {
+        $crate::__alias! {
+            @profile $vis $name $p
+            @entries $entries
+            @docs $docs
+        }
+    };
+    (
+        @profile [$($vis:tt)*] $name:ident $p:ident
+        @entries [$($entries:tt)*]
+        @docs [$($docs:tt)*]
+    ) => {
+        #[doc = concat!("The members of the `", stringify!($p), "` profile, which is active only if all of them are:")]
+        ///
+        $($docs)*
+        $($vis)* const $name: &'static [$crate::Member] = &[$($entries)*];
+    };
+
+    // # Type-Level Boolean
+    (
+        @type_bool [$($vis:tt)*] $ty:ident => $target:ident
+        $(#[$($meta:tt)*])*
+    ) => {
+        $(#[$($meta)*])*
+        #[allow(non_camel_case_types)]
+        $($vis)* type $ty = $crate::$target;
+    };
+
+    // # Marker Trait
+    (
+        @marker_trait [$($vis:tt)*] $name:ident $diagnostic:tt => enabled
+        $(#[$($meta:tt)*])*
+    ) => {
+        $crate::__on_unimplemented! {
+            $diagnostic
+            $(#[$($meta)*])*
+            $($vis)* trait $name {}
+        }
+
+        impl<T: ?$crate::__Sized> $name for T {}
+    };
+    (
+        @marker_trait [$($vis:tt)*] $name:ident $diagnostic:tt => disabled
+        $(#[$($meta:tt)*])*
+    ) => {
+        $crate::__on_unimplemented! {
+            $diagnostic
+            $(#[$($meta)*])*
+            $($vis)* trait $name: $crate::__Unimplemented {}
+        }
+    };
+
+    // # Legacy Export
+    (
+        @export [] => $body:tt
+        @attrs $attrs:tt
+    ) => {};
+    (
+        @export [$export:ident] => $body:tt
+        @attrs $attrs:tt
+    ) => {
+        $crate::__alias_macro! {
+            ($)
+            $attrs
+            @export $export => $body
+        }
+    };
+}
+
+/// Implementation detail of [`alias`].
+/// Applies `#[diagnostic::on_unimplemented(...)]` to a marker trait, if any arguments were provided.
+/// Since this is evaluated within this crate, the attribute is only emitted for compilers which
+/// support it when opted into by building with `--cfg crossfig_diagnostic`.
+#[cfg(crossfig_diagnostic)]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __on_unimplemented {
+    ([] $item:item) => {
+        $item
+    };
+    ([$($diagnostic:tt)+] $item:item) => {
+        #[diagnostic::on_unimplemented($($diagnostic)+)]
+        $item
+    };
+}
+
+/// Implementation detail of [`alias`].
+/// Omits `#[diagnostic::on_unimplemented(...)]`, unless opted into with `--cfg crossfig_diagnostic`.
+#[cfg(not(crossfig_diagnostic))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __on_unimplemented {
+    ($diagnostic:tt $item:item) => {
+        $item
+    };
+}
+
+/// Implementation detail of [`alias`].
+/// Fails to compile unless invoked at the root of a crate.
+#[cfg(not(crossfig_no_core))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __crate_root_only {
+    ($p:ident) => {
+        // Outside of the crate root, this fails with "expected `$p`, found
+        // `PublicAliasMustBeDefinedAtTheCrateRoot`".
+        const _: () = {
+            #[allow(non_camel_case_types)]
+            struct $p;
+
+            #[allow(dead_code)]
+            fn check(alias: $p) -> $p {
+                <$crate::__CrateRoot<{ $crate::__is_crate_root(module_path!()) }> as $crate::__AtCrateRoot<$p>>::check(alias)
+            }
+        };
+    };
+}
+
+/// Implementation detail of [`alias`].
+/// Omits the check for the crate root, as `module_path!` is unavailable without `core`.
+#[cfg(crossfig_no_core)]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __crate_root_only {
+    ($p:ident) => {};
+}
+
+/// Implementation detail of [`alias`].
+/// Defines an alias as its own macro, rather than as a re-export.
+/// This is required for `#[deprecated]` to take effect, for an alias to be available through
+/// `#[macro_use] extern crate ...`, and for an alias to be evaluated at the call site.
+/// Since macros can only be made public with `#[macro_export]`, public aliases defined this way
+/// are exported from the crate root.
+/// A macro exported this way can't be re-exported by path from within its own crate, so public
+/// aliases defined this way are rejected outside of the crate root, rather than silently moved.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __alias_macro {
+    // # Exporting
+    (
+        ($d:tt)
+        $attrs:tt
+        [pub] $p:ident => $body:tt
+    ) => {
+        $crate::__crate_root_only!($p);
+
+        $crate::__alias_macro! {
+            ($d)
+            $attrs
+            @export $p => $body
+        }
+    };
+    (
+        ($d:tt)
+        [$($attrs:tt)*]
+        @export $p:ident => $body:tt
+    ) => {
+        $crate::__alias_macro! {
+            ($d)
+            [$($attrs)* #[macro_export]]
+            [] $p => $body
+        }
+    };
+
+    // # Forwarding to `enabled` or `disabled`
+    (
+        ($d:tt)
+        [$($attrs:tt)*]
+        $vis:tt $p:ident => $target:ident
+    ) => {
+        $crate::__alias_macro! {
+            @define $vis $p {
+                $($attrs)*
+                macro_rules! $p {
+                    ($d($d tokens:tt)*) => {
+                        $crate::$target! { $d($d tokens)* }
+                    };
+                }
+            }
+        }
+    };
+
+    // # Evaluating at the call site
+    (
+        ($d:tt)
+        [$($attrs:tt)*]
+        $vis:tt $p:ident => { $($cond:tt)+ }
+    ) => {
+        $crate::__alias_macro! {
+            @define $vis $p {
+                $($attrs)*
+                macro_rules! $p {
+                    () => {
+                        $crate::switch! {{
+                            $($cond)+ => { true }
+                            _ => { false }
+                        }}
+                    };
+                    (if { $d($d p:tt)* } else { $d($d n:tt)* }) => {
+                        $crate::switch! {
+                            $($cond)+ => { $d($d p)* }
+                            _ => { $d($d n)* }
+                        }
+                    };
+                    ($d($d p:tt)*) => {
+                        $crate::switch! {
+                            $($cond)+ => { $d($d p)* }
+                        }
+                    };
+                }
+            }
+        }
+    };
+
+    // # Re-exporting with a restricted visibility
+    // The macro is defined in a module of the same name, so that re-exporting it doesn't also
+    // re-export a constant of the same name, as defined within a module of aliases.
+    (@define [pub($($restriction:tt)+)] $p:ident { $($definition:tt)* }) => {
+        #[doc(hidden)]
+        #[macro_use]
+        mod $p {
+            $($definition)*
+
+            #[allow(deprecated)]
+            pub(crate) use $p;
+        }
+
+        #[allow(deprecated)]
+        pub($($restriction)+) use self::$p::$p;
+    };
+    // Private aliases are only available textually, like any other `macro_rules` macro.
+    (@define $vis:tt $p:ident { $($definition:tt)* }) => {
+        $($definition)*
+    };
+}
+
+/// Implementation detail of [`alias`].
+/// Defines a module of aliases, along with a constant for each alias, the list of all of them, and
+/// optionally a macro iterating over them.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __alias_mod {
+    // # Attributes
+    (
+        @attrs $attrs:tt
+        @for_each []
+        #[for_each($for_each:ident)]
+        $($rest:tt)*
+    ) => {
+        $crate::__alias_mod! {
+            @attrs $attrs
+            @for_each [$for_each]
+            $($rest)*
+        }
+    };
+    (
+        @attrs [$($attrs:tt)*]
+        @for_each $for_each:tt
+        #[$($m_meta:tt)*]
+        $($rest:tt)*
+    ) => {
+        $crate::__alias_mod! {
+            @attrs [$($attrs)* #[$($m_meta)*]]
+            @for_each $for_each
+            $($rest)*
+        }
+    };
+
+    // # Module
+    (
+        @attrs [$($attrs:tt)*]
+        @for_each []
+        @vis [$($vis:tt)*] mod $name:ident { $($aliases:tt)* }
+    ) => {
+        $($attrs)*
+        $($vis)* mod $name {
+            $crate::alias! {
+                $($aliases)*
+            }
+
+            $crate::__alias_mod! {
+                @members []
+                $($aliases)*
+            }
+        }
+    };
+    // The iterating macro is defined within the module, so the module must be `#[macro_use]` for it
+    // to be visible after the module.
+    (
+        @attrs [$($attrs:tt)*]
+        @for_each [$for_each:ident]
+        @vis [$($vis:tt)*] mod $name:ident { $($aliases:tt)* }
+    ) => {
+        $($attrs)*
+        #[macro_use]
+        $($vis)* mod $name {
+            $crate::alias! {
+                $($aliases)*
+            }
+
+            $crate::__alias_mod! {
+                @members []
+                $($aliases)*
+            }
+
+            // Evaluating a deprecated alias would otherwise raise a warning here.
+            #[allow(deprecated, unused_imports)]
+            #[doc(hidden)]
+            #[macro_use]
+            mod __for_each {
+                use super::*;
+
+                $crate::__alias_mod! {
+                    @for_each ($) [$($vis)*] $for_each $name
+                    @entries []
+                    $($aliases)*
+                }
+            }
+        }
+    };
+
+    // # Iteration
+    (
+        @for_each ($d:tt) [$($vis:tt)*] $for_each:ident $name:ident
+        @entries [$([$p:ident $enabled:tt $cond:tt])*]
+    ) => {
+        $crate::__alias_mod! {
+            @for_each_macro [$($vis)*]
+            #[doc = concat!("Invokes the provided macro once for each alias in `", stringify!($name), "`, in order.")]
+            ///
+            /// Each invocation is passed the name of the alias, whether it is currently active as
+            /// `true` or `false`, and its condition as a `&'static str` expression.
+            macro_rules! $for_each {
+                ($d callback:ident) => {
+                    $($d callback! { $p, $enabled, stringify! $cond })*
+                };
+            }
+        }
+    };
+    (
+        @for_each $d:tt $vis:tt $for_each:ident $name:ident
+        @entries $entries:tt
+        $(#[$m_meta:meta])*
+        $m_vis:vis mod $m_name:ident { $($m_aliases:tt)* }
+        $(, $($rest:tt)*)?
+    ) => {
+        $crate::__alias_mod! {
+            @for_each $d $vis $for_each $name
+            @entries $entries
+            $($($rest)*)?
+        }
+    };
+    (
+        @for_each $d:tt $vis:tt $for_each:ident $name:ident
+        @entries $entries:tt
+        $(#[$($p_meta:tt)*])*
+        $p_vis:vis $p:ident: { $($cond:tt)+ }
+    ) => {
+        $crate::__alias_mod! {
+            @for_each $d $vis $for_each $name
+            @entries $entries
+            $p_vis $p: { $($cond)+ },
+        }
+    };
+    // The alias itself is evaluated, so that overrides are respected.
+    (
+        @for_each $d:tt $vis:tt $for_each:ident $name:ident
+        @entries [$($entries:tt)*]
+        $(#[$($p_meta:tt)*])*
+        $p_vis:vis $p:ident: { $($cond:tt)+ },
+        $($rest:tt)*
+    ) => {
+        $p! {
+            if {
+                $crate::__alias_mod! {
+                    @for_each $d $vis $for_each $name
+                    @entries [$($entries)* [$p true ($($cond)+)]]
+                    $($rest)*
+                }
+            } else {
+                $crate::__alias_mod! {
+                    @for_each $d $vis $for_each $name
+                    @entries [$($entries)* [$p false ($($cond)+)]]
+                    $($rest)*
+                }
+            }
+        }
+    };
+    (@for_each_macro [pub] $item:item) => {
+        #[macro_export]
+        $item
+    };
+    (@for_each_macro [$($vis:tt)*] $item:item) => {
+        $item
+    };
+
+    // # Members
+    (@members [$($members:tt)*]) => {
+        /// Each alias in this module, in order, along with whether it is currently active.
+        #[allow(deprecated)]
+        pub const MEMBERS: &'static [$crate::Member] = &[$($members)*];
+    };
+
+    // Nested modules list their own members
+    (
+        @members $members:tt
+        $(#[$m_meta:meta])*
+        $vis:vis mod $name:ident { $($aliases:tt)* }
+        $(, $($rest:tt)*)?
+    ) => {
+        $crate::__alias_mod! {
+            @members $members
+            $($($rest)*)?
+        }
+    };
+
+    // Single arm with no trailing comma
+    (
+        @members $members:tt
+        $(#[$($p_meta:tt)*])*
+        $vis:vis $p:ident: { $($cond:tt)+ }
+    ) => {
+        $crate::__alias_mod! {
+            @members $members
+            $(#[$($p_meta)*])*
+            $vis $p: { $($cond)+ },
+        }
+    };
+
+    // Some number of arms with trailing comma
+    // The alias itself is evaluated, so that overrides are respected.
+    (
+        @members [$($members:tt)*]
+        $(#[$($p_meta:tt)*])*
+        $vis:vis $p:ident: { $($cond:tt)+ },
+        $($rest:tt)*
+    ) => {
+        #[doc = concat!("Whether [`", stringify!($p), "!`] is currently active.")]
+        #[allow(dead_code, deprecated, non_upper_case_globals)]
+        $vis const $p: bool = $p!();
+
+        $crate::__alias_mod! {
+            @members [
+                $($members)*
+                $crate::Member {
+                    name: stringify!($p),
+                    condition: stringify!($($cond)+),
+                    enabled: $p!(),
+                },
+            ]
+            $($rest)*
+        }
+    };
+}
+
+/// Defines an alias for each of a crate's features, exactly as [`alias`] would.
+///
+/// Each alias is written as its name followed by the name of the feature enabling it, and is
+/// documented with the name of that feature.
+/// Attributes and visibility are passed to [`alias`] unchanged, so aliases defined this way
+/// support everything any other alias does.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate crossfig;
+/// # use crossfig::features;
+/// features! {
+///     pub std = "std",
+///     pub alloc = "alloc",
+///     /// Serialization with `serde`.
+///     pub serde = "serde1",
+/// }
+///
+/// # fn main() {
+/// assert_eq!(serde!(), cfg!(feature = "serde1"));
+/// # }
+/// ```
+///
+/// The name of the feature can't be left out, even when it matches the name of the alias.
+/// `cfg` only accepts a string literal, and macros within it, such as `stringify!`, aren't
+/// expanded, so the literal can't be built from the identifier of the alias:
+///
+/// ```compile_fail
+/// # extern crate crossfig;
+/// # use crossfig::features;
+/// features! {
+///     pub std,
+/// }
+/// // error: the feature enabling `std` must be named, e.g., `std = "std"`
+/// # fn main() {}
+/// ```
+#[macro_export]
+macro_rules! features {
+    // Empty invocations should return nothing
+    () => {};
+
+    // Public visibility is matched separately so it can be inspected by `alias`
+    (
+        $(#[$($p_meta:tt)*])*
+        pub $p:ident = $feature:literal
+        $(, $($rest:tt)*)?
+    ) => {
+        $crate::alias! {
+            #[doc = concat!("Indicates whether the `", $feature, "` feature is enabled.")]
+            ///
+            $(#[$($p_meta)*])*
+            pub $p: { #[cfg(feature = $feature)] }
+        }
+
+        $crate::features! {
+            $($($rest)*)?
+        }
+    };
+    (
+        $(#[$($p_meta:tt)*])*
+        $vis:vis $p:ident = $feature:literal
+        $(, $($rest:tt)*)?
+    ) => {
+        $crate::alias! {
+            #[doc = concat!("Indicates whether the `", $feature, "` feature is enabled.")]
+            ///
+            $(#[$($p_meta)*])*
+            $vis $p: { #[cfg(feature = $feature)] }
+        }
+
+        $crate::features! {
+            $($($rest)*)?
+        }
+    };
+
+    // Common Mistake: omitting the name of the feature
+    (
+        $(#[$($p_meta:tt)*])*
+        $vis:vis $p:ident
+        $(, $($rest:tt)*)?
+    ) => {
+        compile_error!(concat!(
+            "the feature enabling `",
+            stringify!($p),
+            "` must be named, e.g., `",
+            stringify!($p),
+            " = \"",
+            stringify!($p),
+            "\"`",
+        ));
+    };
+}
+
+/// Defines a set of aliases exactly as [`alias`] would, and additionally defines a macro which
+/// evaluates to a `&'static str` Markdown table describing them.
+///
+/// Each row of the table lists an alias's name, its documentation, its condition, and whether it
+/// is currently enabled.
+/// The state of an alias marked `#[overridable(...)]` also notes when an override decided it.
+/// Since the table is built with `concat!`, it can be used wherever a string literal is expected,
+/// including `#[doc = ...]` attributes, so documentation of features can never drift from the
+/// conditions they describe.
+///
+/// The first line names the table macro, and may carry attributes such as `#[macro_export]`.
+/// As with any `macro_rules` item, the table macro can only be used textually _after_ its
+/// definition, so a crate's root `#![doc = ...]` can only use a table exported by another crate.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate crossfig;
+/// crossfig::alias_table! {
+///     macro_rules! feature_table;
+///
+///     /// Indicates whether the `std` feature is enabled.
+///     pub std: { #[cfg(feature = "std")] },
+///     /// Indicates whether the `std` feature is _not_ enabled.
+///     pub no_std: { not(std) },
+/// }
+///
+/// /// The features of this crate:
+/// ///
+/// #[doc = feature_table!()]
+/// pub mod features {}
+///
+/// assert_eq!(feature_table!(), concat!(
+///     "| Alias | Description | Condition | State |\n",
+///     "| --- | --- | --- | --- |\n",
+///     "| `std` | Indicates whether the `std` feature is enabled. | `#[cfg(feature = \"std\")]` | disabled |\n",
+///     "| `no_std` | Indicates whether the `std` feature is _not_ enabled. | `not(std)` | enabled |\n",
+/// ));
+/// ```
+#[macro_export]
+macro_rules! alias_table {
+    (
+        $(#[$t_meta:meta])*
+        macro_rules! $table:ident;
+        $($aliases:tt)*
+    ) => {
+        $crate::alias! {
+            $($aliases)*
+        }
+
+        $crate::__alias_table! {
+            @table [$(#[$t_meta])* $table]
+            @rows []
+            $($aliases)*
+        }
+    };
+}
+
+/// Implementation detail of [`alias_table`].
+/// Evaluates each alias in turn, accumulating its row of the table as string literals.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __alias_table {
+    // All rows evaluated, emit the table macro
+    (
+        @table [$(#[$t_meta:meta])* $table:ident]
+        @rows [$($rows:tt)*]
+    ) => {
+        $(#[$t_meta])*
+        macro_rules! $table {
+            () => {
+                concat!(
+                    "| Alias | Description | Condition | State |\n",
+                    "| --- | --- | --- | --- |\n",
+                    $($rows)*
+                )
+            };
+        }
+    };
+
+    // Single arm with no trailing comma
+    (
+        @table $table:tt
+        @rows $rows:tt
+        $(#[$($p_meta:tt)*])*
+        $vis:vis $p:ident: { $($cond:tt)+ }
+    ) => {
+        $crate::__alias_table! {
+            @table $table
+            @rows $rows
+            $(#[$($p_meta)*])*
+            $vis $p: { $($cond)+ },
+        }
+    };
+
+    // Some number of arms with trailing comma
+    (
+        @table $table:tt
+        @rows $rows:tt
+        $(#[$($p_meta:tt)*])*
+        $vis:vis $p:ident: { $($cond:tt)+ },
+        $($rest:tt)*
+    ) => {
+        $crate::__alias_table! {
+            @row [$p [$([$($p_meta)*])*] [$($cond)+]]
+            @override []
+            @deprecated []
+            @scan [$([$($p_meta)*])*]
+            @table $table
+            @rows $rows
+            $($rest)*
+        }
+    };
+
+    // # Finding any `#[overridable(...)]` or `#[deprecated]` attributes
+    (
+        @row $row:tt
+        @override []
+        @deprecated $deprecated:tt
+        @scan [[overridable($key:tt)] $($metas:tt)*]
+        $($rest:tt)*
+    ) => {
+        $crate::__alias_table! {
+            @row $row
+            @override [$key]
+            @deprecated $deprecated
+            @scan [$($metas)*]
+            $($rest)*
+        }
+    };
+    (
+        @row $row:tt
+        @override $override:tt
+        @deprecated []
+        @scan [[deprecated $($args:tt)*] $($metas:tt)*]
+        $($rest:tt)*
+    ) => {
+        $crate::__alias_table! {
+            @row $row
+            @override $override
+            @deprecated [deprecated]
+            @scan [$($metas)*]
+            $($rest)*
+        }
+    };
+    (
+        @row $row:tt
+        @override $override:tt
+        @deprecated $deprecated:tt
+        @scan [$meta:tt $($metas:tt)*]
+        $($rest:tt)*
+    ) => {
+        $crate::__alias_table! {
+            @row $row
+            @override $override
+            @deprecated $deprecated
+            @scan [$($metas)*]
+            $($rest)*
+        }
+    };
+
+    // # Evaluating the state
+    // ## Decided by an override
+    (
+        @row $row:tt
+        @override [$key:tt]
+        @deprecated $deprecated:tt
+        @scan []
+        $($rest:tt)*
+    ) => {
+        $crate::switch! {
+            #[cfg(crossfig_force_off = $key)] => {
+                $crate::__alias_table! {
+                    @row $row
+                    @state [concat!("disabled (forced off by `--cfg crossfig_force_off=", stringify!($key), "`)")]
+                    $($rest)*
+                }
+            }
+            #[cfg(crossfig_force_on = $key)] => {
+                $crate::__alias_table! {
+                    @row $row
+                    @state [concat!("enabled (forced on by `--cfg crossfig_force_on=", stringify!($key), "`)")]
+                    $($rest)*
+                }
+            }
+            _ => {
+                $crate::__alias_table! {
+                    @row $row
+                    @override []
+                    @deprecated $deprecated
+                    @scan []
+                    $($rest)*
+                }
+            }
+        }
+    };
+    // ## Decided by the alias itself, so that overrides are respected
+    (
+        @row [$p:ident $metas:tt $cond:tt]
+        @override []
+        @deprecated []
+        @scan []
+        $($rest:tt)*
+    ) => {
+        $p! {
+            if {
+                $crate::__alias_table! {
+                    @row [$p $metas $cond]
+                    @state ["enabled"]
+                    $($rest)*
+                }
+            } else {
+                $crate::__alias_table! {
+                    @row [$p $metas $cond]
+                    @state ["disabled"]
+                    $($rest)*
+                }
+            }
+        }
+    };
+    // ## Decided by the condition, as invoking a deprecated alias would warn
+    // Any override has already been checked, so this is equivalent to invoking the alias.
+    (
+        @row [$p:ident $metas:tt [$($cond:tt)+]]
+        @override []
+        @deprecated [deprecated]
+        @scan []
+        $($rest:tt)*
+    ) => {
+        $crate::switch! {
+            $($cond)+ => {
+                $crate::__alias_table! {
+                    @row [$p $metas [$($cond)+]]
+                    @state ["enabled"]
+                    $($rest)*
+                }
+            }
+            _ => {
+                $crate::__alias_table! {
+                    @row [$p $metas [$($cond)+]]
+                    @state ["disabled"]
+                    $($rest)*
+                }
+            }
+        }
+    };
+
+    // # Adding the row
+    (
+        @row [$p:ident [$([$($p_meta:tt)*])*] [$($cond:tt)+]]
+        @state [$state:expr]
+        @table $table:tt
+        @rows [$($rows:tt)*]
+        $($rest:tt)*
+    ) => {
+        $crate::__alias_table! {
+            @table $table
+            @rows [
+                $($rows)*
+                "| `", stringify!($p), "` |",
+                $($crate::__doc_text!($($p_meta)*),)*
+                " | `", stringify!($($cond)+), "` | ", $state, " |\n",
+            ]
+            $($rest)*
+        }
+    };
+}
+
+/// Implementation detail of [`alias_table`].
+/// Evaluates to the text of a `doc` attribute, or an empty string for any other attribute.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __doc_text {
+    (doc = $doc:literal) => {
+        $doc
+    };
+    ($($other:tt)*) => {
+        ""
+    };
+}
+
+/// Conditionally includes the fields of a `struct`, the variants of an `enum`, or the associated
+/// items of an `impl` or `trait` block.
+///
+/// Rust does not allow macros to be invoked in the place of a field or variant, so an alias cannot
+/// be used to gate one directly.
+/// Instead, wrap the whole item in `item`, and mark fields, variants or associated items with
+/// `#[crossfig(...)]`.
+/// The attribute accepts any condition a [`switch`] arm would, including aliases and `all`, `any`
+/// and `not` operations, and is evaluated at the definition site.
+///
+/// Each field, variant and attribute is processed recursively, so very large items may require
+/// raising the `#![recursion_limit]`.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate crossfig;
+/// # use crossfig::{alias, item};
+/// alias! {
+///     std: { #[cfg(feature = "std")] },
+///     alloc: { any(std, #[cfg(feature = "alloc")]) },
+/// }
+///
+/// item! {
+///     #[derive(Default)]
+///     pub struct Buffer {
+///         /// Only present with `alloc`.
+///         #[crossfig(alloc)]
+///         pub heap: Vec<u8>,
+///         #[crossfig(not(alloc))]
+///         pub stack: [u8; 16],
+///         pub len: usize,
+///     }
+/// }
+///
+/// item! {
+///     pub struct Handle(pub usize, #[crossfig(std)] pub std::fs::File);
+/// }
+///
+/// item! {
+///     pub enum Backend {
+///         #[crossfig(std)]
+///         Threads(usize),
+///         Inline,
+///     }
+/// }
+///
+/// # fn main() {
+/// let buffer = Buffer::default();
+/// assert_eq!(buffer.stack, [0; 16]);
+///
+/// let Handle(_) = Handle(0);
+///
+/// match Backend::Inline {
+///     Backend::Inline => {}
+/// }
+/// # }
+///
+/// item! {
+///     impl Buffer {
+///         #[crossfig(alloc)]
+///         pub fn as_slice(&self) -> &[u8] {
+///             &self.heap
+///         }
+///         #[crossfig(not(alloc))]
+///         pub fn as_slice(&self) -> &[u8] {
+///             &self.stack[..self.len]
+///         }
+///     }
+/// }
+/// ```
+#[macro_export]
+macro_rules! item {
+    (
+        $(#[$($attr:tt)*])*
+        $vis:vis struct $name:ident
+        $($rest:tt)*
+    ) => {
+        $crate::__item! {
+            @head fields [$(#[$($attr)*])* $vis struct $name]
+            $($rest)*
+        }
+    };
+    (
+        $(#[$($attr:tt)*])*
+        $vis:vis enum $name:ident
+        $($rest:tt)*
+    ) => {
+        $crate::__item! {
+            @head variants [$(#[$($attr)*])* $vis enum $name]
+            $($rest)*
+        }
+    };
+    (
+        $(#[$($attr:tt)*])*
+        $vis:vis trait $name:ident
+        $($rest:tt)*
+    ) => {
+        $crate::__item! {
+            @head items [$(#[$($attr)*])* $vis trait $name]
+            $($rest)*
+        }
+    };
+    (
+        $(#[$($attr:tt)*])*
+        $vis:vis unsafe trait $name:ident
+        $($rest:tt)*
+    ) => {
+        $crate::__item! {
+            @head items [$(#[$($attr)*])* $vis unsafe trait $name]
+            $($rest)*
+        }
+    };
+    (
+        $(#[$($attr:tt)*])*
+        impl
+        $($rest:tt)*
+    ) => {
+        $crate::__item! {
+            @head items [$(#[$($attr)*])* impl]
+            $($rest)*
+        }
+    };
+    (
+        $(#[$($attr:tt)*])*
+        unsafe impl
+        $($rest:tt)*
+    ) => {
+        $crate::__item! {
+            @head items [$(#[$($attr)*])* unsafe impl]
+            $($rest)*
+        }
+    };
+}
+
+/// Conditionally includes the arms of a `match` expression.
+///
+/// Like fields and variants, `match` arms cannot be produced by a macro.
+/// Instead, wrap the whole expression in `expr`, and mark arms with `#[crossfig(...)]`, exactly
+/// as with [`item`].
+/// Combined with aliases exported from a dependency, this allows matching on variants which only
+/// exist when that dependency's features are enabled.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate crossfig;
+/// # use crossfig::{alias, expr, item};
+/// // In the `foo` crate:
+/// # mod foo {
+/// # use crossfig::{alias, item};
+/// alias! {
+///     pub spin: { #[cfg(feature = "spin")] },
+/// }
+///
+/// item! {
+///     pub enum Backend {
+///         #[crossfig(spin)]
+///         Spin,
+///         Std,
+///     }
+/// }
+/// # }
+///
+/// // In a consumer's crate:
+/// # fn main() {
+/// let name = expr! {
+///     match foo::Backend::Std {
+///         #[crossfig(foo::spin)]
+///         foo::Backend::Spin => "spin",
+///         foo::Backend::Std => "std",
+///     }
+/// };
+///
+/// assert_eq!(name, "std");
+/// # }
+/// ```
+#[macro_export]
+macro_rules! expr {
+    (
+        match $($rest:tt)*
+    ) => {
+        $crate::__item! {
+            @head arms [match]
+            $($rest)*
+        }
+    };
+}
+
+/// Implementation detail of [`item`] and [`expr`].
+/// Splits an item or expression into its head and body, then evaluates each element of the body
+/// in turn, accumulating those which are enabled.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __item {
+    // # Head
+    // ## Match arms
+    (
+        @head arms [$($head:tt)*]
+        { $($body:tt)* }
+    ) => {
+        $crate::__item! {
+            @next arms
+            @emit [expr [$($head)*]]
+            @done []
+            $($body)*
+        }
+    };
+    // ## Named fields, variants, or associated items
+    (
+        @head $kind:ident [$($head:tt)*]
+        { $($body:tt)* }
+    ) => {
+        $crate::__item! {
+            @next $kind
+            @emit [braces [$($head)*]]
+            @done []
+            $($body)*
+        }
+    };
+    // ## Tuple fields
+    (
+        @head fields [$($head:tt)*]
+        ($($body:tt)*);
+    ) => {
+        $crate::__item! {
+            @next tuple_fields
+            @emit [parens [$($head)*] [;]]
+            @done []
+            $($body)*
+        }
+    };
+    (
+        @head fields [$($head:tt)*]
+        ($($body:tt)*) where $($tail:tt)*
+    ) => {
+        $crate::__item! {
+            @next tuple_fields
+            @emit [parens [$($head)*] [where $($tail)*]]
+            @done []
+            $($body)*
+        }
+    };
+    // ## Generics, where clauses, and scrutinees
+    (
+        @head $kind:ident [$($head:tt)*]
+        $token:tt $($rest:tt)*
+    ) => {
+        $crate::__item! {
+            @head $kind [$($head)* $token]
+            $($rest)*
+        }
+    };
+
+    // # Body
+    // ## All elements evaluated
+    (
+        @next $kind:ident
+        @emit [$context:ident [$($head:tt)*]]
+        @done [$($done:tt)*]
+    ) => {
+        $($head)* { $($done)* }
+    };
+    (
+        @next $kind:ident
+        @emit [parens [$($head:tt)*] [$($tail:tt)*]]
+        @done [$($done:tt)*]
+    ) => {
+        $($head)* ( $($done)* ) $($tail)*
+    };
+    // ## Named field
+    (
+        @next fields
+        @emit $emit:tt
+        @done $done:tt
+        $(#[$($attr:tt)*])*
+        $vis:vis $field:ident: $ty:ty
+        $(, $($rest:tt)*)?
+    ) => {
+        $crate::__item! {
+            @scan [] []
+            $(#[$($attr)*])*
+            @element [$vis $field: $ty,]
+            @then fields @emit $emit @done $done
+            @rest [$($($rest)*)?]
+        }
+    };
+    // ## Tuple field
+    (
+        @next tuple_fields
+        @emit $emit:tt
+        @done $done:tt
+        $(#[$($attr:tt)*])*
+        $vis:vis $ty:ty
+        $(, $($rest:tt)*)?
+    ) => {
+        $crate::__item! {
+            @scan [] []
+            $(#[$($attr)*])*
+            @element [$vis $ty,]
+            @then tuple_fields @emit $emit @done $done
+            @rest [$($($rest)*)?]
+        }
+    };
+    // ## Variant
+    (
+        @next variants
+        @emit $emit:tt
+        @done $done:tt
+        $(#[$($attr:tt)*])*
+        $variant:ident $(($($fields:tt)*))? $({$($named:tt)*})? $(= $discriminant:expr)?
+        $(, $($rest:tt)*)?
+    ) => {
+        $crate::__item! {
+            @scan [] []
+            $(#[$($attr)*])*
+            @element [$variant $(($($fields)*))? $({$($named)*})? $(= $discriminant)?,]
+            @then variants @emit $emit @done $done
+            @rest [$($($rest)*)?]
+        }
+    };
+    // ## Associated items and match arms
+    // ### Attributes
+    (
+        @next $kind:ident
+        @emit $emit:tt
+        @done $done:tt
+        @attrs [$($attrs:tt)*]
+        #[$($attr:tt)*]
+        $($rest:tt)*
+    ) => {
+        $crate::__item! {
+            @next $kind
+            @emit $emit
+            @done $done
+            @attrs [$($attrs)* #[$($attr)*]]
+            $($rest)*
+        }
+    };
+    (
+        @next $kind:ident
+        @emit $emit:tt
+        @done $done:tt
+        @attrs $attrs:tt
+        $($rest:tt)+
+    ) => {
+        $crate::__item! {
+            @collect $kind [] []
+            @attrs $attrs @emit $emit @done $done
+            $($rest)+
+        }
+    };
+    (
+        @next $kind:ident
+        @emit $emit:tt
+        @done $done:tt
+        $($rest:tt)+
+    ) => {
+        $crate::__item! {
+            @next $kind
+            @emit $emit
+            @done $done
+            @attrs []
+            $($rest)+
+        }
+    };
+    // ### Associated item ending in a `;`
+    (
+        @collect items [$($tokens:tt)*] $fn:tt
+        @attrs [$($attrs:tt)*] @emit $emit:tt @done $done:tt
+        ; $($rest:tt)*
+    ) => {
+        $crate::__item! {
+            @scan [] []
+            $($attrs)*
+            @element [$($tokens)* ;]
+            @then items @emit $emit @done $done
+            @rest [$($rest)*]
+        }
+    };
+    // ### Associated function ending in a body
+    (
+        @collect items [$($tokens:tt)*] [fn]
+        @attrs [$($attrs:tt)*] @emit $emit:tt @done $done:tt
+        { $($body:tt)* } $($rest:tt)*
+    ) => {
+        $crate::__item! {
+            @scan [] []
+            $($attrs)*
+            @element [$($tokens)* { $($body)* }]
+            @then items @emit $emit @done $done
+            @rest [$($rest)*]
+        }
+    };
+    // ### Macro invocation
+    (
+        @collect items [$($tokens:tt)*] $fn:tt
+        @attrs [$($attrs:tt)*] @emit $emit:tt @done $done:tt
+        ! { $($body:tt)* } $($rest:tt)*
+    ) => {
+        $crate::__item! {
+            @scan [] []
+            $($attrs)*
+            @element [$($tokens)* ! { $($body)* }]
+            @then items @emit $emit @done $done
+            @rest [$($rest)*]
+        }
+    };
+    // ### Associated function
+    (
+        @collect items [$($tokens:tt)*] $fn:tt
+        @attrs $attrs:tt @emit $emit:tt @done $done:tt
+        fn $($rest:tt)*
+    ) => {
+        $crate::__item! {
+            @collect items [$($tokens)* fn] [fn]
+            @attrs $attrs @emit $emit @done $done
+            $($rest)*
+        }
+    };
+    // ### Match arm with a block
+    (
+        @collect arms [$($tokens:tt)*] $fn:tt
+        @attrs [$($attrs:tt)*] @emit $emit:tt @done $done:tt
+        => { $($body:tt)* }, $($rest:tt)*
+    ) => {
+        $crate::__item! {
+            @scan [] []
+            $($attrs)*
+            @element [$($tokens)* => { $($body)* }]
+            @then arms @emit $emit @done $done
+            @rest [$($rest)*]
+        }
+    };
+    (
+        @collect arms [$($tokens:tt)*] $fn:tt
+        @attrs [$($attrs:tt)*] @emit $emit:tt @done $done:tt
+        => { $($body:tt)* } $($rest:tt)*
+    ) => {
+        $crate::__item! {
+            @scan [] []
+            $($attrs)*
+            @element [$($tokens)* => { $($body)* }]
+            @then arms @emit $emit @done $done
+            @rest [$($rest)*]
+        }
+    };
+    // ### Match arm with an expression
+    (
+        @collect arms [$($tokens:tt)*] $fn:tt
+        @attrs [$($attrs:tt)*] @emit $emit:tt @done $done:tt
+        => $body:expr $(, $($rest:tt)*)?
+    ) => {
+        $crate::__item! {
+            @scan [] []
+            $($attrs)*
+            @element [$($tokens)* => $body,]
+            @then arms @emit $emit @done $done
+            @rest [$($($rest)*)?]
+        }
+    };
+    // ### Other
+    (
+        @collect $kind:ident [$($tokens:tt)*] $fn:tt
+        @attrs $attrs:tt @emit $emit:tt @done $done:tt
+        $token:tt $($rest:tt)*
+    ) => {
+        $crate::__item! {
+            @collect $kind [$($tokens)* $token] $fn
+            @attrs $attrs @emit $emit @done $done
+            $($rest)*
+        }
+    };
+
+    // # Attributes
+    // ## Condition
+    (
+        @scan $attrs:tt []
+        #[crossfig($($cond:tt)+)]
+        $($rest:tt)*
+    ) => {
+        $crate::__item! {
+            @scan $attrs [$($cond)+]
+            $($rest)*
+        }
+    };
+    // ## Additional conditions must all be active
+    (
+        @scan $attrs:tt [$($prev:tt)+]
+        #[crossfig($($cond:tt)+)]
+        $($rest:tt)*
+    ) => {
+        $crate::__item! {
+            @scan $attrs [all($($prev)+, $($cond)+)]
+            $($rest)*
+        }
+    };
+    // ## Other
+    (
+        @scan [$($attrs:tt)*] $cond:tt
+        #[$($attr:tt)*]
+        $($rest:tt)*
+    ) => {
+        $crate::__item! {
+            @scan [$($attrs)* #[$($attr)*]] $cond
+            $($rest)*
+        }
+    };
+    // ## Unconditional element
+    (
+        @scan [$($attrs:tt)*] []
+        @element [$($element:tt)*]
+        @then $kind:ident @emit $emit:tt @done [$($done:tt)*]
+        @rest [$($rest:tt)*]
+    ) => {
+        $crate::__item! {
+            @next $kind
+            @emit $emit
+            @done [$($done)* $($attrs)* $($element)*]
+            $($rest)*
+        }
+    };
+    // ## Conditional element in an expression
+    (
+        @scan [$($attrs:tt)*] [$($cond:tt)+]
+        @element [$($element:tt)*]
+        @then $kind:ident @emit [expr $($emit:tt)*] @done [$($done:tt)*]
+        @rest [$($rest:tt)*]
+    ) => {
+        $crate::switch! {{
+            $($cond)+ => {
+                $crate::__item! {
+                    @next $kind
+                    @emit [expr $($emit)*]
+                    @done [$($done)* $($attrs)* $($element)*]
+                    $($rest)*
+                }
+            }
+            _ => {
+                $crate::__item! {
+                    @next $kind
+                    @emit [expr $($emit)*]
+                    @done [$($done)*]
+                    $($rest)*
+                }
+            }
+        }}
+    };
+    // ## Conditional element in an item
+    (
+        @scan [$($attrs:tt)*] [$($cond:tt)+]
+        @element [$($element:tt)*]
+        @then $kind:ident @emit $emit:tt @done [$($done:tt)*]
+        @rest [$($rest:tt)*]
+    ) => {
+        $crate::switch! {
+            $($cond)+ => {
+                $crate::__item! {
+                    @next $kind
+                    @emit $emit
+                    @done [$($done)* $($attrs)* $($element)*]
+                    $($rest)*
+                }
+            }
+            _ => {
+                $crate::__item! {
+                    @next $kind
+                    @emit $emit
+                    @done [$($done)*]
+                    $($rest)*
+                }
+            }
+        }
+    };
+}
+
+/// Conditionally applies attributes to an item, similar to `#[cfg_attr(...)]`.
+///
+/// Each pair of a condition and a list of attributes is separated by `;`, and followed by the item
+/// they apply to.
+/// Conditions accept anything a [`switch`] arm would, including aliases exported by other crates,
+/// and `all`, `any` and `not` operations.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate crossfig;
+/// # use crossfig::{alias, attr_if};
+/// alias! {
+///     debug: { #[cfg(debug_assertions)] },
+///     small: { #[cfg(target_pointer_width = "16")] },
+/// }
+///
+/// attr_if! {
+///     debug => derive(Debug);
+///     small => repr(u8);
+///     not(small) => repr(u32), derive(Clone, Copy);
+///     pub enum Level {
+///         Error,
+///         Warn,
+///     }
+/// }
+/// # fn main() {}
+/// ```
+#[macro_export]
+macro_rules! attr_if {
+    ($($tokens:tt)*) => {
+        $crate::__attr_if! {
+            @attrs []
+            @cond []
+            $($tokens)*
+        }
+    };
+}
+
+/// Implementation detail of [`attr_if`].
+/// Scans for each condition up to its `=>`, evaluates it, and accumulates the attributes of those
+/// which are active.
+/// Anything left once no `=>` remains is the item.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __attr_if {
+    // # Condition
+    (
+        @attrs [$($attrs:tt)*]
+        @cond [$($cond:tt)+]
+        => $($attr:meta),+;
+        $($rest:tt)*
+    ) => {
+        $crate::switch! {
+            $($cond)+ => {
+                $crate::__attr_if! {
+                    @attrs [$($attrs)* $(#[$attr])+]
+                    @cond []
+                    $($rest)*
+                }
+            }
+            _ => {
+                $crate::__attr_if! {
+                    @attrs [$($attrs)*]
+                    @cond []
+                    $($rest)*
+                }
+            }
+        }
+    };
+    (
+        @attrs $attrs:tt
+        @cond [$($cond:tt)*]
+        $token:tt $($rest:tt)*
+    ) => {
+        $crate::__attr_if! {
+            @attrs $attrs
+            @cond [$($cond)* $token]
+            $($rest)*
+        }
+    };
+
+    // # Item
+    (
+        @attrs [$($attrs:tt)*]
+        @cond [$($item:tt)*]
+    ) => {
+        $($attrs)*
+        $($item)*
+    };
+}
+
+/// Defines a marker trait which requires the provided bounds only while a condition is active.
+///
+/// The trait is implemented for all types satisfying its bounds, so it can be used in place of
+/// those bounds in generic code.
+/// When the condition is inactive, the trait has no bounds and is implemented for _all_ types.
+/// Conditions accept anything a [`switch`] arm would, including aliases exported by other crates.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate crossfig;
+/// # use crossfig::{alias, maybe_trait};
+/// alias! {
+///     parallel: { #[cfg(feature = "parallel")] }
+/// }
+///
+/// maybe_trait! {
+///     /// Requires `Send` when `parallel` is enabled.
+///     pub MaybeSend: Send if parallel
+/// }
+///
+/// maybe_trait! {
+///     pub MaybeSendSync: Send + Sync if parallel
+/// }
+///
+/// fn spawn<T: MaybeSend + 'static>(task: T) {
+///     // ...
+/// #   let _ = task;
+/// }
+/// # fn main() { spawn(0_u8); }
+/// ```
+#[macro_export]
+macro_rules! maybe_trait {
+    ($(#[$meta:meta])* $vis:vis $name:ident: $($rest:tt)+) => {
+        $crate::__maybe_trait! {
+            @attrs [$(#[$meta])*]
+            @vis [$vis]
+            @name $name
+            @bounds []
+            $($rest)+
+        }
+    };
+}
+
+/// Implementation detail of [`maybe_trait`].
+/// Collects the bounds up to `if`, then defines the trait based on the remaining condition.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __maybe_trait {
+    (
+        @attrs [$($attrs:tt)*]
+        @vis [$vis:vis]
+        @name $name:ident
+        @bounds [$($bound:tt)+]
+        if $($cond:tt)+
+    ) => {
+        $crate::switch! {
+            $($cond)+ => {
+                $($attrs)*
+                #[doc = ""]
+                #[doc = concat!("Requires `", stringify!($($bound)+), "` because `", stringify!($($cond)+), "` is currently active.")]
+                $vis trait $name: $($bound)+ {}
+
+                impl<T: ?$crate::__Sized + $($bound)+> $name for T {}
+            }
+            _ => {
+                $($attrs)*
+                #[doc = ""]
+                #[doc = concat!("Implemented for all types because `", stringify!($($cond)+), "` is currently inactive.")]
+                $vis trait $name {}
+
+                impl<T: ?$crate::__Sized> $name for T {}
+            }
+        }
+    };
+    (
+        @attrs $attrs:tt
+        @vis $vis:tt
+        @name $name:ident
+        @bounds [$($bound:tt)*]
+        $token:tt $($rest:tt)*
+    ) => {
+        $crate::__maybe_trait! {
+            @attrs $attrs
+            @vis $vis
+            @name $name
+            @bounds [$($bound)* $token]
+            $($rest)*
+        }
+    };
+    (
+        @attrs $attrs:tt
+        @vis $vis:tt
+        @name $name:ident
+        @bounds $bounds:tt
+    ) => {
+        compile_error!(concat!(
+            "expected `if` followed by a condition after the bounds of `",
+            stringify!($name),
+            "`",
+        ));
+    };
+}
+
+/// Defines a function whose signature is always available, but whose body depends on a condition.
+///
+/// While the condition is active, the function uses the provided body.
+/// Otherwise, a stub with the same signature is defined according to the policy after `else`:
+/// - `else unimplemented`, which panics when called.
+/// - `else default { ... }`, which uses the provided fallback body instead.
+///
+/// Both versions share the provided documentation and attributes, with a note on the condition.
+/// Conditions accept anything a [`switch`] arm would, including aliases exported by other crates.
+/// This allows a public API to remain the same regardless of which features are enabled, avoiding
+/// breakage when crates in the same build disagree on features.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate crossfig;
+/// # use crossfig::{alias, gated_fn};
+/// alias! {
+///     std: { #[cfg(feature = "std")] },
+/// }
+///
+/// gated_fn! {
+///     std =>
+///     /// Returns the number of available threads.
+///     pub fn threads() -> usize {
+///         # /*
+///         std::thread::available_parallelism().map_or(1, |n| n.get())
+///         # */
+///         # 1
+///     }
+///     else default {
+///         1
+///     }
+/// }
+///
+/// gated_fn! {
+///     std =>
+///     /// Prints the provided message.
+///     pub fn print(message: &str) {
+///         # /*
+///         std::println!("{message}");
+///         # */
+///     }
+///     else unimplemented
+/// }
+/// # fn main() {
+/// # assert_eq!(threads(), 1);
+/// # }
+/// ```
+///
+/// Since a `compile_error!` can't be deferred until a function is called, there is no policy for
+/// raising a compilation error on use.
+/// Instead, bound the function on a marker trait defined by an alias marked
+/// `#[marker_trait(...)]`, as described in [`alias`].
+///
+/// If the condition is a plain `#[cfg(...)]`, both versions are also marked
+/// `#[cfg_attr(docsrs, doc(cfg(...)))]`, so rustdoc shows the condition as a badge when
+/// documenting with `--cfg docsrs`, as docs.rs does.
+/// The crate using `gated_fn` must then enable `#![cfg_attr(docsrs, feature(doc_cfg))]`.
+/// Other conditions, such as aliases, can't be shown as a badge, since rustdoc can't evaluate them.
+///
+/// ```
+/// # #![cfg_attr(docsrs, feature(doc_cfg))]
+/// # extern crate crossfig;
+/// # use crossfig::gated_fn;
+/// gated_fn! {
+///     #[cfg(unix)] =>
+///     /// Returns the process ID of the parent process.
+///     pub fn parent_id() -> u32 {
+///         # /*
+///         std::os::unix::process::parent_id()
+///         # */
+///         # 1
+///     }
+///     else unimplemented
+/// }
+/// # fn main() {}
+/// ```
+#[macro_export]
+macro_rules! gated_fn {
+    ($($tokens:tt)*) => {
+        $crate::__gated_fn! {
+            @scan []
+            $($tokens)*
+        }
+    };
+}
+
+/// Implementation detail of [`gated_fn`].
+/// Scans the condition up to its `=>`, then the signature up to the body preceding `else`.
+/// Rules for later stages come first, as the scanning rules would otherwise consume their state.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __gated_fn {
+    // # Condition
+    // A plain `#[cfg(...)]` can also be shown by rustdoc's badge.
+    (
+        @scan [#[cfg($meta:meta)]]
+        => $($rest:tt)*
+    ) => {
+        $crate::__gated_fn! {
+            @cond [#[cfg($meta)]]
+            @attrs [#[cfg_attr(docsrs, doc(cfg($meta)))]]
+            @sig []
+            $($rest)*
+        }
+    };
+    (
+        @scan [$($cond:tt)+]
+        => $($rest:tt)*
+    ) => {
+        $crate::__gated_fn! {
+            @cond [$($cond)+]
+            @attrs []
+            @sig []
+            $($rest)*
+        }
+    };
+    (
+        @scan [$($cond:tt)*]
+        $token:tt $($rest:tt)*
+    ) => {
+        $crate::__gated_fn! {
+            @scan [$($cond)* $token]
+            $($rest)*
+        }
+    };
+
+    // # Policy
+    (
+        @cond [$($cond:tt)+]
+        @attrs $attrs:tt
+        @sig [$($sig:tt)+]
+        @body $body:block
+        @policy [unimplemented]
+    ) => {
+        $crate::__gated_fn! {
+            @cond [$($cond)+]
+            @attrs $attrs
+            @sig [$($sig)+]
+            @body $body
+            @stub {
+                panic!(concat!("not implemented: requires `", stringify!($($cond)+), "`"))
+            }
+            @doc [
+                #[doc = concat!("**Requires `", stringify!($($cond)+), "`**, which is _not_ currently active, so this function panics.")]
+            ]
+        }
+    };
+    (
+        @cond [$($cond:tt)+]
+        @attrs $attrs:tt
+        @sig [$($sig:tt)+]
+        @body $body:block
+        @policy [default $stub:block]
+    ) => {
+        $crate::__gated_fn! {
+            @cond [$($cond)+]
+            @attrs $attrs
+            @sig [$($sig)+]
+            @body $body
+            @stub $stub
+            @doc [
+                #[doc = concat!("**Requires `", stringify!($($cond)+), "`**, which is _not_ currently active, so this function uses a fallback.")]
+            ]
+        }
+    };
+    (
+        @cond $cond:tt
+        @attrs $attrs:tt
+        @sig $sig:tt
+        @body $body:block
+        @policy [$($policy:tt)+]
+    ) => {
+        compile_error!(concat!(
+            "unknown fallback policy `",
+            stringify!($($policy)+),
+            "`, expected `unimplemented` or `default { ... }`",
+        ));
+    };
+
+    // # Definition
+    (
+        @cond [$($cond:tt)+]
+        @attrs [$($attrs:tt)*]
+        @sig [$($sig:tt)+]
+        @body $body:block
+        @stub $stub:block
+        @doc [$($doc:tt)*]
+    ) => {
+        $crate::switch! {
+            $($cond)+ => {
+                $($attrs)*
+                ///
+                #[doc = concat!("**Requires `", stringify!($($cond)+), "`**, which is currently active.")]
+                $($sig)+
+                $body
+            }
+            _ => {
+                $($attrs)*
+                ///
+                $($doc)*
+                #[allow(unused_variables)]
+                $($sig)+
+                $stub
+            }
+        }
+    };
+
+    // # Signature
+    (
+        @cond $cond:tt
+        @attrs [$($attrs:tt)*]
+        @sig []
+        #[$($meta:tt)*]
+        $($rest:tt)*
+    ) => {
+        $crate::__gated_fn! {
+            @cond $cond
+            @attrs [$($attrs)* #[$($meta)*]]
+            @sig []
+            $($rest)*
+        }
+    };
+    (
+        @cond $cond:tt
+        @attrs $attrs:tt
+        @sig [$($sig:tt)+]
+        $body:block
+        else $($policy:tt)+
+    ) => {
+        $crate::__gated_fn! {
+            @cond $cond
+            @attrs $attrs
+            @sig [$($sig)+]
+            @body $body
+            @policy [$($policy)+]
+        }
+    };
+    (
+        @cond $cond:tt
+        @attrs $attrs:tt
+        @sig [$($sig:tt)*]
+        $token:tt $($rest:tt)*
+    ) => {
+        $crate::__gated_fn! {
+            @cond $cond
+            @attrs $attrs
+            @sig [$($sig)* $token]
+            $($rest)*
+        }
+    };
+    (
+        @cond [$($cond:tt)+]
+        @attrs $attrs:tt
+        @sig $sig:tt
+    ) => {
+        compile_error!("expected a function body followed by `else unimplemented` or `else default { ... }`");
+    };
+
+}
+
+/// Defines an enum of only the enabled backends, allowing one to be chosen at runtime.
+///
+/// Each arm names a variant, followed by a condition and a block using the same grammar as
+/// [`switch`].
+/// Unlike [`switch`], _every_ enabled arm is kept, and the function signature after the enum's name
+/// is implemented by matching on `self` and running the block of the corresponding variant.
+/// The enabled variants are also available as `VARIANTS`, and their names as `ENABLED`, both in
+/// order of declaration.
+/// An enabled variant can be chosen by name with `from_name`, such as from a command line
+/// argument.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate crossfig;
+/// # use crossfig::{alias, dispatch};
+/// alias! {
+///     fast: { #[cfg(feature = "fast")] },
+/// }
+///
+/// dispatch! {
+///     /// Hashers available in this build.
+///     #[derive(Clone, Copy, Debug)]
+///     pub enum Hasher: pub fn hash(self, bytes: &[u8]) -> u64 {
+///         /// Requires the `fast` feature.
+///         Fast: fast => {
+///             bytes.len() as u64
+///         }
+///         Simple: all() => {
+///             bytes.iter().map(|&byte| byte as u64).sum()
+///         }
+///     }
+/// }
+///
+/// # fn main() {
+/// assert_eq!(Hasher::ENABLED.last(), Some(&"Simple"));
+/// assert_eq!(Hasher::VARIANTS.len(), Hasher::ENABLED.len());
+/// assert_eq!(Hasher::Simple.hash(&[1, 2, 3]), 6);
+///
+/// let hasher = Hasher::from_name("Simple").unwrap();
+/// assert_eq!(hasher.hash(&[1, 2, 3]), 6);
+/// assert!(Hasher::from_name("Unknown").is_none());
+/// # }
+/// ```
+///
+/// If no arm is enabled, a compilation error is raised.
+///
+/// ```compile_fail
+/// # extern crate crossfig;
+/// # use crossfig::dispatch;
+/// dispatch! {
+///     pub enum Backend: pub fn run(self) {
+///         Never: any() => {}
+///     }
+/// }
+/// # fn main() {}
+/// ```
+#[macro_export]
+macro_rules! dispatch {
+    (
+        $(#[$($meta:tt)*])*
+        $vis:vis enum $name:ident: $($rest:tt)+
+    ) => {
+        $crate::__dispatch! {
+            @enum [$(#[$($meta)*])*] [$vis] $name
+            @sig [] []
+            $($rest)+
+        }
+    };
+}
+
+/// Implementation detail of [`dispatch`].
+/// Scans the signature up to the braced arms, then evaluates each arm's condition, keeping only
+/// those which are enabled.
+/// Rules for later stages come first, as the scanning rules would otherwise consume their state.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __dispatch {
+    // # Definition
+    (
+        @enum $attrs:tt $vis:tt $name:ident
+        @sig $sig:tt $self:tt
+        @enabled []
+    ) => {
+        compile_error!(concat!("no variant of `", stringify!($name), "` is enabled"));
+    };
+    (
+        @enum $attrs:tt $vis:tt $name:ident
+        @sig $sig:tt []
+        @enabled $enabled:tt
+    ) => {
+        compile_error!(concat!("the signature of `", stringify!($name), "` must take `self`"));
+    };
+    (
+        @enum [$($attrs:tt)*] [$vis:vis] $name:ident
+        @sig [$($sig:tt)+] [$self:tt]
+        @enabled [$([$(#[$($meta:tt)*])* $variant:ident { $($body:tt)* }])+]
+    ) => {
+        $($attrs)*
+        $vis enum $name {
+            $(
+                $(#[$($meta)*])*
+                $variant,
+            )+
+        }
+
+        impl $name {
+            /// The names of the enabled variants, in order of declaration.
+            $vis const ENABLED: &'static [&'static str] = &[$(stringify!($variant)),+];
+
+            /// The enabled variants, in order of declaration.
+            $vis const VARIANTS: &'static [Self] = &[$($name::$variant),+];
+
+            /// Returns the enabled variant with the provided name, if any.
+            $vis fn from_name(name: &str) -> $crate::__Option<Self> {
+                $(
+                    if name == stringify!($variant) {
+                        return $crate::__Option::Some($name::$variant);
+                    }
+                )+
+                $crate::__Option::None
+            }
+
+            $($sig)+ {
+                match $self {
+                    $($name::$variant => { $($body)* })+
+                }
+            }
+        }
+    };
+
+    // # Arms
+    (
+        @enum $enum:tt $vis:tt $name:ident
+        @sig $sig:tt $self:tt
+        @enabled $enabled:tt
+        _ => $($rest:tt)*
+    ) => {
+        compile_error!(concat!(
+            "`",
+            stringify!($name),
+            "` can't have a wildcard arm, since all enabled arms are kept",
+        ));
+    };
+    (
+        @enum $enum:tt $vis:tt $name:ident
+        @sig $sig:tt $self:tt
+        @enabled $enabled:tt
+        $(#[$($meta:tt)*])*
+        $variant:ident: $($rest:tt)+
+    ) => {
+        $crate::__dispatch! {
+            @enum $enum $vis $name
+            @sig $sig $self
+            @enabled $enabled
+            @variant [$(#[$($meta)*])* $variant]
+            @cond []
+            $($rest)+
+        }
+    };
+
+    // ## Condition
+    (
+        @enum $enum:tt $vis:tt $name:ident
+        @sig $sig:tt $self:tt
+        @enabled [$($enabled:tt)*]
+        @variant [$($variant:tt)+]
+        @cond [$($cond:tt)+]
+        => { $($body:tt)* },
+        $($rest:tt)*
+    ) => {
+        $crate::__dispatch! {
+            @enum $enum $vis $name
+            @sig $sig $self
+            @enabled [$($enabled)*]
+            @variant [$($variant)+]
+            @cond [$($cond)+]
+            => { $($body)* }
+            $($rest)*
+        }
+    };
+    (
+        @enum $enum:tt $vis:tt $name:ident
+        @sig $sig:tt $self:tt
+        @enabled [$($enabled:tt)*]
+        @variant [$($variant:tt)+]
+        @cond [$($cond:tt)+]
+        => { $($body:tt)* }
+        $($rest:tt)*
+    ) => {
+        $crate::switch! {
+            $($cond)+ => {
+                $crate::__dispatch! {
+                    @enum $enum $vis $name
+                    @sig $sig $self
+                    @enabled [$($enabled)* [$($variant)+ { $($body)* }]]
+                    $($rest)*
+                }
+            }
+            _ => {
+                $crate::__dispatch! {
+                    @enum $enum $vis $name
+                    @sig $sig $self
+                    @enabled [$($enabled)*]
+                    $($rest)*
+                }
+            }
+        }
+    };
+    (
+        @enum $enum:tt $vis:tt $name:ident
+        @sig $sig:tt $self:tt
+        @enabled $enabled:tt
+        @variant $variant:tt
+        @cond [$($cond:tt)*]
+        $token:tt $($rest:tt)*
+    ) => {
+        $crate::__dispatch! {
+            @enum $enum $vis $name
+            @sig $sig $self
+            @enabled $enabled
+            @variant $variant
+            @cond [$($cond)* $token]
+            $($rest)*
+        }
+    };
+
+    // # Signature
+    (
+        @enum $enum:tt $vis:tt $name:ident
+        @sig [$($sig:tt)+] $self:tt
+        { $($arms:tt)* }
+    ) => {
+        $crate::__dispatch! {
+            @enum $enum $vis $name
+            @sig [$($sig)+] $self
+            @enabled []
+            $($arms)*
+        }
+    };
+    // ## Receiver
+    // `self` must be taken from the input to be usable in the generated `match`, so each group of
+    // parentheses is matched twice: once to identify the shape of a receiver, and once to capture it.
+    (
+        @enum $enum:tt $vis:tt $name:ident
+        @sig [$($sig:tt)*] []
+        ($($args:tt)*) $($rest:tt)+
+    ) => {
+        $crate::__dispatch! {
+            @enum $enum $vis $name
+            @sig [$($sig)*] []
+            @receiver ($($args)*) ($($args)*)
+            $($rest)+
+        }
+    };
+    (
+        @enum $enum:tt $vis:tt $name:ident
+        @sig [$($sig:tt)*] []
+        @receiver (self $($_args:tt)*) ($self:ident $($args:tt)*)
+        $($rest:tt)+
+    ) => {
+        $crate::__dispatch! {
+            @enum $enum $vis $name
+            @sig [$($sig)* ($self $($args)*)] [$self]
+            $($rest)+
+        }
+    };
+    (
+        @enum $enum:tt $vis:tt $name:ident
+        @sig [$($sig:tt)*] []
+        @receiver (mut self $($_args:tt)*) (mut $self:ident $($args:tt)*)
+        $($rest:tt)+
+    ) => {
+        $crate::__dispatch! {
+            @enum $enum $vis $name
+            @sig [$($sig)* (mut $self $($args)*)] [$self]
+            $($rest)+
+        }
+    };
+    (
+        @enum $enum:tt $vis:tt $name:ident
+        @sig [$($sig:tt)*] []
+        @receiver (& self $($_args:tt)*) (& $self:ident $($args:tt)*)
+        $($rest:tt)+
+    ) => {
+        $crate::__dispatch! {
+            @enum $enum $vis $name
+            @sig [$($sig)* (& $self $($args)*)] [$self]
+            $($rest)+
+        }
+    };
+    (
+        @enum $enum:tt $vis:tt $name:ident
+        @sig [$($sig:tt)*] []
+        @receiver (& mut self $($_args:tt)*) (& mut $self:ident $($args:tt)*)
+        $($rest:tt)+
+    ) => {
+        $crate::__dispatch! {
+            @enum $enum $vis $name
+            @sig [$($sig)* (& mut $self $($args)*)] [$self]
+            $($rest)+
+        }
+    };
+    (
+        @enum $enum:tt $vis:tt $name:ident
+        @sig [$($sig:tt)*] []
+        @receiver (& $_lt:lifetime self $($_args:tt)*) (& $lt:lifetime $self:ident $($args:tt)*)
+        $($rest:tt)+
+    ) => {
+        $crate::__dispatch! {
+            @enum $enum $vis $name
+            @sig [$($sig)* (& $lt $self $($args)*)] [$self]
+            $($rest)+
+        }
+    };
+    (
+        @enum $enum:tt $vis:tt $name:ident
+        @sig [$($sig:tt)*] []
+        @receiver (& $_lt:lifetime mut self $($_args:tt)*) (& $lt:lifetime mut $self:ident $($args:tt)*)
+        $($rest:tt)+
+    ) => {
+        $crate::__dispatch! {
+            @enum $enum $vis $name
+            @sig [$($sig)* (& $lt mut $self $($args)*)] [$self]
+            $($rest)+
+        }
+    };
+    (
+        @enum $enum:tt $vis:tt $name:ident
+        @sig [$($sig:tt)*] []
+        @receiver $_args:tt $args:tt
+        $($rest:tt)+
+    ) => {
+        $crate::__dispatch! {
+            @enum $enum $vis $name
+            @sig [$($sig)* $args] []
+            $($rest)+
+        }
+    };
+    (
+        @enum $enum:tt $vis:tt $name:ident
+        @sig [$($sig:tt)*] $self:tt
+        $token:tt $($rest:tt)+
+    ) => {
+        $crate::__dispatch! {
+            @enum $enum $vis $name
+            @sig [$($sig)* $token] $self
+            $($rest)+
+        }
+    };
+}
+
+/// Selects a block based on the target features available, similar to [`switch`].
+///
+/// Each arm names a target feature, such as `"avx2"`, and arms are evaluated top to bottom, with a
+/// required wildcard arm as the fallback.
+/// An arm whose feature is statically enabled (e.g., with `-C target-feature=+avx2`) is selected
+/// at compile time.
+///
+/// Otherwise, if the condition in an optional leading `#[detect(...)]` is active, the arm's feature
+/// is checked at runtime with `std::is_x86_feature_detected!`, selecting the first arm whose
+/// feature is detected.
+/// This condition should indicate `std` is available to the calling crate, and accepts anything a
+/// [`switch`] arm would.
+/// Runtime detection is only performed on `x86` and `x86_64` targets.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate crossfig;
+/// # use crossfig::{alias, simd_switch};
+/// alias! {
+///     std: { #[cfg(all())] },
+/// }
+///
+/// # fn main() {
+/// let lanes = simd_switch! {
+///     #[detect(std)]
+///     "avx2" => { 8 }
+///     "sse2" => { 4 }
+///     _ => { 1 }
+/// };
+/// # let _ = lanes;
+/// # }
+/// ```
+///
+/// Note that a block selected at runtime is compiled _without_ its target feature enabled, so should
+/// call functions marked `#[target_feature(enable = "...")]` to benefit from it.
+#[macro_export]
+macro_rules! simd_switch {
+    (
+        #[detect($($detect:tt)+)]
+        $($arms:tt)+
+    ) => {
+        $crate::__simd_switch! {
+            @detect [$($detect)+]
+            $($arms)+
+        }
+    };
+    ($($arms:tt)+) => {
+        $crate::__simd_switch! {
+            @detect [any()]
+            $($arms)+
+        }
+    };
+}
+
+/// Implementation detail of [`simd_switch`].
+/// Each arm is selected statically if its feature is enabled, or otherwise checked at runtime
+/// before falling through to the remaining arms.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __simd_switch {
+    // # Arms
+    (
+        @detect $detect:tt
+        _ => { $($fallback:tt)* } $(,)?
+    ) => {
+        { $($fallback)* }
+    };
+    (
+        @detect $detect:tt
+        $feature:tt => { $($block:tt)* },
+        $($rest:tt)+
+    ) => {
+        $crate::__simd_switch! {
+            @detect $detect
+            $feature => { $($block)* }
+            $($rest)+
+        }
+    };
+    (
+        @detect $detect:tt
+        $feature:tt => { $($block:tt)* }
+        $($rest:tt)+
+    ) => {
+        $crate::switch! {{
+            #[cfg(target_feature = $feature)] => {
+                $($block)*
+            }
+            _ => {
+                if $crate::__simd_switch!(@detected $detect $feature) {
+                    $($block)*
+                } else {
+                    $crate::__simd_switch! {
+                        @detect $detect
+                        $($rest)+
+                    }
+                }
+            }
+        }}
+    };
+    (
+        @detect $detect:tt
+        $($arms:tt)*
+    ) => {
+        compile_error!("expected arms of the form `\"feature\" => { ... }`, ending with a wildcard arm `_ => { ... }`");
+    };
+
+    // # Runtime Detection
+    (@detected [$($detect:tt)+] $feature:tt) => {
+        $crate::switch! {{
+            all(
+                $($detect)+,
+                any(#[cfg(target_arch = "x86")], #[cfg(target_arch = "x86_64")]),
+            ) => {
+                ::std::is_x86_feature_detected!($feature)
+            }
+            _ => { false }
+        }}
+    };
+}
+
+// Relaxing `Sized` requires the `Sized` lang item, and `core` isn't in the extern prelude of
+// consumers on older compilers.
+#[cfg(not(crossfig_no_core))]
+#[doc(hidden)]
+pub use core::marker::Sized as __Sized;
+
+// `Option` isn't in scope for consumers using `#![no_implicit_prelude]`.
+#[cfg(not(crossfig_no_core))]
+#[doc(hidden)]
+pub use core::option::Option as __Option;
+
+/// Implementation detail of [`alias`].
+/// Returns whether the provided `module_path!()` names the root of a crate.
+#[cfg(not(crossfig_no_core))]
+#[doc(hidden)]
+pub const fn __is_crate_root(module_path: &str) -> bool {
+    let bytes = module_path.as_bytes();
+    let mut index = 0;
+    while index < bytes.len() {
+        if bytes[index] == b':' {
+            return false;
+        }
+        index += 1;
+    }
+    true
+}
+
+/// Implementation detail of [`alias`].
+/// Whether an alias is defined at the crate root, as returned by [`__is_crate_root`].
+#[cfg(not(crossfig_no_core))]
+#[doc(hidden)]
+pub struct __CrateRoot<const AT_CRATE_ROOT: bool>;
+
+/// Implementation detail of [`alias`].
+/// Named in the error raised when a public alias which must be defined at the crate root isn't.
+#[cfg(not(crossfig_no_core))]
+#[doc(hidden)]
+pub struct PublicAliasMustBeDefinedAtTheCrateRoot;
+
+/// Implementation detail of [`alias`].
+/// Returns the alias unchanged at the crate root, or [`PublicAliasMustBeDefinedAtTheCrateRoot`]
+/// anywhere else, so a mismatched type names both the alias and the problem.
+#[cfg(not(crossfig_no_core))]
+#[doc(hidden)]
+pub trait __AtCrateRoot<T> {
+    type Output;
+
+    fn check(alias: T) -> Self::Output;
+}
+
+#[cfg(not(crossfig_no_core))]
+impl<T> __AtCrateRoot<T> for __CrateRoot<true> {
+    type Output = T;
+
+    fn check(alias: T) -> T {
+        alias
+    }
+}
+
+#[cfg(not(crossfig_no_core))]
+impl<T> __AtCrateRoot<T> for __CrateRoot<false> {
+    type Output = PublicAliasMustBeDefinedAtTheCrateRoot;
+
+    fn check(_: T) -> Self::Output {
+        PublicAliasMustBeDefinedAtTheCrateRoot
+    }
+}
+
+/// Implementation detail of [`alias`].
+/// A supertrait of the marker traits of disabled aliases, which is implemented for no types.
+/// It is sealed, so that those marker traits can't be implemented outside of this crate either.
+/// Traits require the `Sized` lang item, so this is unavailable without `core`.
+#[cfg(not(crossfig_no_core))]
+#[doc(hidden)]
+pub trait __Unimplemented: unimplemented::Sealed {}
+
+#[cfg(not(crossfig_no_core))]
+mod unimplemented {
+    pub trait Sealed {}
+}
+
+// Reporting the selected arm requires `Option` and `Display`, so is unavailable without `core`.
+#[cfg(not(crossfig_no_core))]
+pub use self::selected::Selected;
+
+// Lists of members are slices, so are unavailable without `core`.
+#[cfg(not(crossfig_no_core))]
+pub use self::member::Member;
+
+#[cfg(not(crossfig_no_core))]
+mod member {
+    /// A member of a profile marked `#[profile(NAME)]`, or an alias in a module of aliases.
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    pub struct Member {
+        /// The name of the member, as written.
+        pub name: &'static str,
+        /// The condition of the member.
+        /// For a member of a profile, this is the same as its name.
+        pub condition: &'static str,
+        /// Whether the member is currently active.
+        pub enabled: bool,
+    }
+}
+
+#[cfg(not(crossfig_no_core))]
+mod selected {
+    use core::fmt;
+    use core::option::Option::{self, None, Some};
+    use core::write;
+
+    /// Describes the arm chosen by a [`switch_selected`](crate::switch_selected).
+    ///
+    /// When displayed, this is formatted as `label (arm 0, `condition`)`, or `arm 0, `condition``
+    /// for an arm without a label.
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    pub struct Selected {
+        /// The position of the arm in the switch, starting from `0`.
+        pub index: usize,
+        /// The label of the arm, if it has one.
+        pub label: Option<&'static str>,
+        /// The condition of the arm as written, or `_` for the wildcard.
+        pub condition: &'static str,
+    }
+
+    impl Selected {
+        #[doc(hidden)]
+        pub const fn __unlabelled(index: usize, condition: &'static str) -> Self {
+            Self {
+                index,
+                label: None,
+                condition,
+            }
+        }
+
+        #[doc(hidden)]
+        pub const fn __labelled(
+            index: usize,
+            label: &'static str,
+            condition: &'static str,
+        ) -> Self {
+            Self {
+                index,
+                label: Some(label),
+                condition,
+            }
+        }
+    }
+
+    impl fmt::Display for Selected {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            match self.label {
+                Some(label) => write!(f, "{} (arm {}, `{}`)", label, self.index, self.condition),
+                None => write!(f, "arm {}, `{}`", self.index, self.condition),
+            }
+        }
+    }
+}
+
+// Type-level booleans require the `Sized` lang item, so are unavailable without `core`.
+#[cfg(not(crossfig_no_core))]
+pub use self::type_bool::{Bool, False, If, Select, True};
+
+#[cfg(not(crossfig_no_core))]
+mod type_bool {
+    /// A type-level boolean, either [`True`] or [`False`].
+    ///
+    /// Aliases marked `#[type_bool(name)]` define `name` as one of these types, allowing generic code
+    /// to select types and implementations through the type system.
+    /// See [`If`] for selecting between two types.
+    ///
+    /// This trait is sealed, and cannot be implemented outside of this crate.
+    pub trait Bool: sealed::Sealed {
+        /// The `bool` value of this type.
+        const VALUE: bool;
+
+        /// The negation of this type.
+        type Not: Bool;
+    }
+
+    /// The type-level boolean `true`.
+    pub struct True;
+
+    /// The type-level boolean `false`.
+    pub struct False;
+
+    impl Bool for True {
+        const VALUE: bool = true;
+
+        type Not = False;
+    }
+
+    impl Bool for False {
+        const VALUE: bool = false;
+
+        type Not = True;
+    }
+
+    /// Selects between two types based on a type-level boolean.
+    /// See [`If`] for a more convenient form.
+    pub trait Select<Then, Else>: Bool {
+        /// `Then` if `Self` is [`True`], otherwise `Else`.
+        type Output;
+    }
+
+    impl<Then, Else> Select<Then, Else> for True {
+        type Output = Then;
+    }
+
+    impl<Then, Else> Select<Then, Else> for False {
+        type Output = Else;
+    }
+
+    /// `Then` if `Cond` is [`True`], otherwise `Else`.
+    ///
+    /// This is a type alias rather than an associated type of [`Bool`], since generic associated types
+    /// require Rust 1.65, above the MSRV of this crate.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate crossfig;
+    /// # use crossfig::{alias, If};
+    /// alias! {
+    ///     #[type_bool(wide_ty)]
+    ///     wide: { #[cfg(target_pointer_width = "64")] },
+    /// }
+    ///
+    /// type Word = If<wide_ty, u64, u32>;
+    ///
+    /// # fn main() {
+    /// assert_eq!(core::mem::size_of::<Word>() * 8, if wide!() { 64 } else { 32 });
+    /// # }
+    /// ```
+    pub type If<Cond, Then, Else> = <Cond as Select<Then, Else>>::Output;
+
+    mod sealed {
+        pub trait Sealed {}
+
+        impl Sealed for super::True {}
+
+        impl Sealed for super::False {}
+    }
+}
+
+#[cfg(test)]
+mod alias_tests {
+    #![allow(unused_imports)]
+
+    use super::alias;
+
+    alias! {
+        a: { #[cfg(all())] },
+        b: { #[cfg(all())] },
+        c: { a },
+        d: { all(a, b, c) },
+        e: { any(not(a), b, all(c), #[cfg(test)]) },
+        pub f: { e },
+    }
+}
+
+#[cfg(test)]
+mod deprecated_alias_tests {
+    #![allow(deprecated, unused_imports, clippy::needless_late_init)]
+
+    use super::{alias, switch};
+
+    alias! {
+        a: { #[cfg(all())] },
+        #[deprecated(note = "use `a` instead")]
+        /// Documentation may follow the deprecation.
+        b: { a },
+        /// Or precede it.
+        #[deprecated]
+        c: { not(a) },
+        d: { any(b, c) },
+    }
+
+    #[test]
+    fn evaluates_as_before() {
+        assert!(b!());
+        assert!(!c!());
+        assert!(d!());
+
+        let _b: bool;
+        b! {
+            if {
+                _b = true;
+            } else {
+                _b = false;
+            }
+        }
+        assert!(_b);
+
+        switch! {
+            c => {
+                compile_error!("expected skip");
+            }
+            b => {
+                let _b = true;
+            }
+            _ => {
+                compile_error!("expected skip");
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod overridable_alias_tests {
+    #![allow(unused_imports, unused_macros, clippy::bool_comparison)]
+
+    use super::{alias, alias_table, Bool};
+    use core::iter::Iterator;
+    use core::option::Option::Some;
+
+    // Run with `--cfg crossfig_force_on="..."` or `--cfg crossfig_force_off="..."` to test overrides.
+    alias! {
+        #[type_bool(on_ty)]
+        #[overridable("crossfig::on")]
+        on: { #[cfg(all())] },
+        #[overridable("crossfig::off")]
+        #[type_bool(off_ty)]
+        off: { any() },
+        #[call_site]
+        #[overridable("crossfig::call_site")]
+        call_site: { any() },
+    }
+
+    #[test]
+    fn definition_site() {
+        let on = !cfg!(crossfig_force_off = "crossfig::on");
+        assert!(on!() == on);
+        assert!(on_ty::VALUE == on);
+
+        let off = cfg!(crossfig_force_on = "crossfig::off")
+            && !cfg!(crossfig_force_off = "crossfig::off");
+        assert!(off!() == off);
+        assert!(off_ty::VALUE == off);
+    }
+
+    #[test]
+    fn call_site() {
+        let call_site = cfg!(crossfig_force_on = "crossfig::call_site")
+            && !cfg!(crossfig_force_off = "crossfig::call_site");
+        assert!(call_site!() == call_site);
+    }
+
+    alias_table! {
+        macro_rules! table;
+
+        #[overridable("crossfig::table")]
+        in_table: { #[cfg(all())] },
+        #[deprecated]
+        deprecated_in_table: { in_table },
+    }
+
+    #[test]
+    fn table() {
+        let state = if cfg!(crossfig_force_off = "crossfig::table") {
+            "disabled (forced off by `--cfg crossfig_force_off=\"crossfig::table\"`)"
+        } else if cfg!(crossfig_force_on = "crossfig::table") {
+            "enabled (forced on by `--cfg crossfig_force_on=\"crossfig::table\"`)"
+        } else {
+            "enabled"
+        };
+        let states = |index| {
+            let row = table!()
+                .lines()
+                .nth(index)
+                .and_then(|row| row.strip_suffix(" |"));
+            row.and_then(|row| row.rsplit("| ").next())
+        };
+        assert!(states(2) == Some(state));
+
+        let deprecated = if cfg!(crossfig_force_off = "crossfig::table") {
+            "disabled"
+        } else {
+            "enabled"
+        };
+        assert!(states(3) == Some(deprecated));
+    }
+}
+
+#[cfg(test)]
+mod call_site_alias_tests {
+    #![allow(unused_imports, clippy::needless_late_init)]
+
+    use super::{alias, switch};
+
+    alias! {
+        #[call_site]
+        a: { #[cfg(test)] },
+        /// Documentation may precede the marker.
+        #[call_site]
+        b: { not(#[cfg(test)]) },
+    }
+
+    #[test]
+    fn as_bool() {
+        assert!(a!());
+        assert!(!b!());
+    }
+
+    #[test]
+    fn as_block() {
+        let _a: ();
+        a! {
+            _a = ();
+        }
+        b! {
+            compile_error!("expected skip");
+        }
+    }
+
+    #[test]
+    fn as_if() {
+        let _a: ();
+        a! {
+            if {
+                _a = ();
+            } else {
+                compile_error!("expected skip");
+            }
+        }
+    }
+
+    #[test]
+    fn as_switch_arm() {
+        let _a: ();
+        switch! {
+            b => {
+                compile_error!("expected skip");
+            }
+            a => {
+                _a = ();
+            }
+            _ => {
+                compile_error!("expected skip");
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod item_tests {
+    #![allow(unused_imports, dead_code)]
+
+    use super::{alias, item};
+
+    alias! {
+        a: { #[cfg(all())] },
+        b: { not(a) },
+    }
+
+    item! {
+        /// Named fields.
+        #[derive(Clone, Copy)]
+        pub(crate) struct Named<'a, T: ::core::marker::Copy, const N: usize>
+        where
+            T: ::core::clone::Clone,
+        {
+            /// Kept.
+            #[crossfig(a)]
+            pub kept: &'a [T; N],
+            #[crossfig(b)]
+            pub dropped: (),
+            #[crossfig(#[cfg(test)])]
+            #[crossfig(any(b, a))]
+            both: u8,
+            #[crossfig(a)]
+            #[crossfig(b)]
+            neither: (),
+            always: (T, T),
+        }
+    }
+
+    item! {
+        struct Tuple<T>(#[crossfig(a)] pub T, #[crossfig(b)] (), u8)
+        where
+            T: ::core::marker::Copy;
+    }
+
+    item! {
+        struct Unit(#[crossfig(b)] u8);
+    }
+
+    item! {
+        #[repr(u8)]
+        enum Discriminants {
+            #[crossfig(not(b))]
+            Kept = 1,
+            #[crossfig(b)]
+            Dropped = 2,
+        }
+    }
+
+    item! {
+        enum Variants {
+            #[crossfig(not(b))]
+            Kept,
+            #[crossfig(b)]
+            Dropped,
+            #[crossfig(a)]
+            Tuple(u8, u16),
+            #[crossfig(a)]
+            Named { field: u8 },
+            Always,
+        }
+    }
+
+    item! {
+        struct Wide {
+            /// Field
+            #[crossfig(a)]
+            f0: (),
+            /// Field
+            #[crossfig(a)]
+            f1: (),
+            /// Field
+            #[crossfig(a)]
+            f2: (),
+            /// Field
+            #[crossfig(a)]
+            f3: (),
+            /// Field
+            #[crossfig(a)]
+            f4: (),
+            /// Field
+            #[crossfig(a)]
+            f5: (),
+            /// Field
+            #[crossfig(a)]
+            f6: (),
+            /// Field
+            #[crossfig(a)]
+            f7: (),
+            /// Field
+            #[crossfig(a)]
+            f8: (),
+            /// Field
+            #[crossfig(a)]
+            f9: (),
+        }
+    }
+
+    #[test]
+    fn fields() {
+        let named = Named::<u8, 2> {
+            kept: &[1, 2],
+            both: 3,
+            always: (4, 5),
+        };
+        assert!(named.kept[1] == 2);
+
+        let Tuple(kept, last) = Tuple(1u8, 2);
+        assert!(kept == 1 && last == 2);
+
+        let Unit() = Unit();
+    }
+
+    #[test]
+    fn variants() {
+        assert!(Discriminants::Kept as u8 == 1);
+
+        match (Variants::Named { field: 1 }) {
+            Variants::Kept | Variants::Tuple(..) | Variants::Always => {}
+            Variants::Named { field } => assert!(field == 1),
+        }
+    }
+
+    #[test]
+    fn wide() {
+        let Wide {
+            f0: (),
+            f1: (),
+            f2: (),
+            f3: (),
+            f4: (),
+            f5: (),
+            f6: (),
+            f7: (),
+            f8: (),
+            f9: (),
+        } = Wide {
+            f0: (),
+            f1: (),
+            f2: (),
+            f3: (),
+            f4: (),
+            f5: (),
+            f6: (),
+            f7: (),
+            f8: (),
+            f9: (),
+        };
+    }
+}
+
+#[cfg(test)]
+mod item_impl_tests {
+    #![allow(unused_imports, dead_code)]
+
+    use super::{alias, expr, item};
+
+    alias! {
+        a: { #[cfg(all())] },
+        b: { not(a) },
+    }
+
+    item! {
+        enum Backend {
+            #[crossfig(a)]
+            Kept(u8),
+            #[crossfig(b)]
+            Dropped,
+            Always,
+        }
+    }
+
+    item! {
+        /// Associated items.
+        pub trait Trait: ::core::marker::Sized {
+            /// Kept.
+            #[crossfig(a)]
+            const KEPT: u8;
+            #[crossfig(b)]
+            const DROPPED: u8;
+            #[crossfig(a)]
+            type Kept;
+            #[crossfig(b)]
+            fn dropped(&self);
+            #[crossfig(a)]
+            fn kept(&self) -> u8 {
+                Self::KEPT
+            }
+            fn always(self) -> Self {
+                self
+            }
+        }
+    }
+
+    item! {
+        impl Trait for Backend {
+            #[crossfig(a)]
+            const KEPT: u8 = 1;
+            #[crossfig(b)]
+            const DROPPED: u8 = 2;
+            #[crossfig(a)]
+            type Kept = ();
+            #[crossfig(b)]
+            fn dropped(&self) {}
+        }
+    }
+
+    item! {
+        impl<T> Generic<T>
+        where
+            T: ::core::marker::Copy,
+        {
+            #[crossfig(a)]
+            pub fn kept(self) -> T {
+                self.0
+            }
+            #[crossfig(b)]
+            pub fn kept(self) -> u8 {
+                0
+            }
+        }
+    }
+
+    struct Generic<T>(T);
+
+    fn name(backend: &Backend) -> &'static str {
+        expr! {
+            match backend {
+                #[crossfig(a)]
+                Backend::Kept(0) => "zero",
+                #[crossfig(a)]
+                Backend::Kept(n) if *n > 10 => {
+                    "many"
+                }
+                #[crossfig(a)]
+                Backend::Kept(_) => { "some" },
+                #[crossfig(b)]
+                Backend::Dropped => "dropped",
+                #[crossfig(#[cfg(test)])]
+                Backend::Always => "always"
+            }
+        }
+    }
+
+    #[test]
+    fn associated_items() {
+        let backend = Backend::Kept(1);
+        assert!(backend.kept() == 1);
+        assert!(name(&Backend::Always.always()) == "always");
+        assert!(Generic(2u16).kept() == 2);
+    }
+
+    #[test]
+    fn arms() {
+        assert!(name(&Backend::Kept(0)) == "zero");
+        assert!(name(&Backend::Kept(11)) == "many");
+        assert!(name(&Backend::Kept(1)) == "some");
+        assert!(name(&Backend::Always) == "always");
+    }
+}
+
+#[cfg(test)]
+mod attr_if_tests {
+    #![allow(unused_imports, dead_code)]
+
+    use super::{alias, attr_if};
+
+    alias! {
+        a: { #[cfg(all())] },
+        b: { not(a) },
+    }
+
+    attr_if! {
+        a => repr(u8);
+        b => repr(u16);
+        enum Single {
+            A,
+        }
+    }
+
+    attr_if! {
+        b => repr(u8);
+        any(b, #[cfg(test)]) => repr(u16), derive(::core::clone::Clone, ::core::marker::Copy);
+        #[cfg(test)] => derive(::core::fmt::Debug);
+        /// Documented.
+        #[derive(::core::cmp::PartialEq)]
+        pub(crate) enum Multiple {
+            A,
+        }
+    }
+
+    attr_if! {
+        b => derive(::core::clone::Clone);
+        struct Unchanged;
+    }
+
+    fn implements<T: ::core::clone::Clone + ::core::fmt::Debug + ::core::cmp::PartialEq>() {}
+
+    #[test]
+    fn applied() {
+        assert!(::core::mem::size_of::<Single>() == 1);
+        assert!(::core::mem::size_of::<Multiple>() == 2);
+        implements::<Multiple>();
+    }
+}
+
+#[cfg(test)]
+mod maybe_trait_tests {
+    #![allow(unused_imports)]
+
+    use super::{alias, maybe_trait};
+    use core::any::Any;
+
+    alias! {
+        parallel: { #[cfg(all())] },
+        serial: { not(parallel) },
+    }
+
+    maybe_trait! {
+        /// Documented.
+        pub MaybeSend: ::core::marker::Send if parallel
+    }
+
+    maybe_trait! {
+        pub(crate) MaybeSendSync: ::core::marker::Send + ::core::marker::Sync if any(parallel, #[cfg(test)])
+    }
+
+    maybe_trait! {
+        MaybeNothing: ::core::marker::Send if serial
+    }
+
+    fn send<T: MaybeSendSync>() {}
+
+    fn anything<T: MaybeNothing>() {}
+
+    fn requires_send<T: MaybeSend>(value: T) -> impl ::core::marker::Send {
+        value
+    }
+
+    #[test]
+    fn bounds() {
+        send::<u8>();
+        anything::<*const u8>();
+        let _ = requires_send(0_u8);
+    }
+
+    fn unsized_send<T: ?::core::marker::Sized + MaybeSendSync>(_: &T) {}
+
+    fn unsized_anything<T: ?::core::marker::Sized + MaybeNothing>(_: &T) {}
+
+    #[test]
+    fn unsized_bounds() {
+        unsized_send("str");
+        unsized_send::<[u8]>(&[0]);
+        unsized_anything::<dyn Any>(&0_u8);
+    }
+}
+
+#[cfg(test)]
+mod type_bool_tests {
+    #![allow(unused_imports, clippy::bool_comparison)]
+
+    use super::{alias, Bool, False, If, Select, True};
+
+    alias! {
+        /// Documented.
+        #[type_bool(a_ty)]
+        pub a: { #[cfg(all())] },
+        /// Documented before the type.
+        #[type_bool(b_ty)]
+        b: { not(a) },
+    }
+
+    fn size<C: Select<u8, u16>>() -> usize {
+        ::core::mem::size_of::<If<C, u8, u16>>()
+    }
+
+    #[test]
+    fn values() {
+        assert!(a_ty::VALUE == a!());
+        assert!(b_ty::VALUE == b!());
+        assert!(!<a_ty as Bool>::Not::VALUE);
+        assert!(<<b_ty as Bool>::Not as Bool>::VALUE);
+    }
+
+    #[test]
+    fn selection() {
+        assert!(size::<a_ty>() == 1);
+        assert!(size::<b_ty>() == 2);
+        assert!(::core::mem::size_of::<If<True, u8, u16>>() == 1);
+        assert!(::core::mem::size_of::<If<False, u8, u16>>() == 2);
+    }
+}
+
+#[cfg(test)]
+mod profile_tests {
+    #![allow(unused_imports)]
+
+    use super::{alias, Member};
+
+    alias! {
+        a: { #[cfg(all())] },
+        b: { #[cfg(any())] },
+        #[profile(BOTH)]
+        both: { all(a, not(b)) },
+        #[profile(MISSING)]
+        pub(crate) missing: { all(a, b, #[cfg(all())],) },
+    }
+
+    #[test]
+    fn all_active() {
+        assert!(both!());
+        assert!(
+            BOTH == [
+                Member {
+                    name: "a",
+                    condition: "a",
+                    enabled: true,
+                },
+                Member {
+                    name: "not(b)",
+                    condition: "not(b)",
+                    enabled: true,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn one_missing() {
+        assert!(!missing!());
+        assert!(MISSING.len() == 3);
+        assert!(MISSING[1].name == "b" && !MISSING[1].enabled);
+        assert!(MISSING[2].name == "#[cfg(all())]" && MISSING[2].enabled);
+    }
+}
+
+#[cfg(test)]
+mod marker_trait_tests {
+    #![allow(unused_imports, unused_macros)]
+
+    use super::alias;
+
+    alias! {
+        #[marker_trait(A)]
+        pub a: { #[cfg(all())] },
+        /// Documented.
+        #[marker_trait(B)]
+        b: { not(a) },
+    }
+
+    fn only_a<T: A>(value: T) -> T {
+        value
+    }
+
+    // Always exists, but can't be called.
+    #[allow(dead_code)]
+    fn only_b<T: B>(value: T) -> T {
+        value
+    }
+
+    fn only_unsized_a<T: ?::core::marker::Sized + A>(_: &T) {}
+
+    #[test]
+    fn bounds() {
+        assert!(only_a(1_u8) == 1);
+        only_unsized_a("str");
+    }
+}
+
+#[cfg(test)]
+mod gated_fn_tests {
+    #![allow(unused_imports, dead_code)]
+
+    use super::{alias, gated_fn};
+
+    alias! {
+        a: { #[cfg(all())] },
+        b: { not(a) },
+    }
+
+    gated_fn! {
+        a =>
+        /// Documented.
+        #[inline]
+        pub fn enabled(value: u8) -> u8 {
+            value + 1
+        }
+        else unimplemented
+    }
+
+    gated_fn! {
+        b =>
+        pub(crate) fn fallback<T>(value: T) -> (T, bool)
+        where
+            T: ::core::marker::Copy,
+        {
+            (value, true)
+        }
+        else default {
+            (value, false)
+        }
+    }
+
+    gated_fn! {
+        #[cfg(all())] =>
+        /// Shown with a badge.
+        pub fn badge(value: u8) -> u8 {
+            value + 2
+        }
+        else unimplemented
+    }
+
+    gated_fn! {
+        any(b, not(a)) =>
+        fn unimplemented(value: u8) -> u8 {
+            value
+        }
+        else unimplemented
+    }
+
+    #[test]
+    fn bodies() {
+        assert!(enabled(1) == 2);
+        assert!(fallback(1) == (1, false));
+        assert!(badge(1) == 3);
+    }
+
+    #[test]
+    #[should_panic = "not implemented: requires `any(b, not(a))`"]
+    fn stub() {
+        unimplemented(1);
+    }
+}
+
+#[cfg(test)]
+mod dispatch_tests {
+    #![allow(unused_imports)]
+
+    use super::{alias, dispatch};
+
+    alias! {
+        a: { #[cfg(all())] },
+        b: { not(a) },
+    }
+
+    dispatch! {
+        /// Documented.
+        #[derive(::core::clone::Clone, ::core::marker::Copy)]
+        pub(crate) enum Backend: pub(crate) fn run(&self, value: u8) -> u8 {
+            /// First.
+            First: a => {
+                value + 1
+            },
+            Skipped: b => {
+                value + 2
+            },
+            Third: any(b, #[cfg(test)]) => {
+                let base = value;
+                base * 2
+            }
+        }
+    }
+
+    dispatch! {
+        enum ByValue: fn name<'a>(mut self, names: &'a [&'a str]) -> &'a str {
+            Only: a => {
+                self = ByValue::Only;
+                names[0]
+            }
+        }
+    }
 
-        $crate::alias! {
-            $($rest)*
+    #[test]
+    fn enabled() {
+        assert!(Backend::ENABLED == ["First", "Third"]);
+        assert!(ByValue::ENABLED == ["Only"]);
+    }
+
+    #[test]
+    fn run() {
+        assert!(Backend::First.run(1) == 2);
+        assert!(Backend::Third.run(2) == 4);
+        assert!(ByValue::Only.name(ByValue::ENABLED) == "Only");
+    }
+
+    #[test]
+    fn variants() {
+        assert!(Backend::VARIANTS.len() == 2);
+        assert!(Backend::VARIANTS[0].run(3) == 4);
+        assert!(Backend::VARIANTS[1].run(3) == 6);
+        assert!(ByValue::VARIANTS.len() == 1);
+    }
+
+    #[test]
+    fn from_name() {
+        let chosen = "Third";
+        match Backend::from_name(chosen) {
+            ::core::option::Option::Some(backend) => assert!(backend.run(5) == 10),
+            ::core::option::Option::None => panic!("`Third` is enabled"),
         }
-    };
+        assert!(Backend::from_name("First").is_some());
+        assert!(Backend::from_name("Skipped").is_none());
+        assert!(Backend::from_name("third").is_none());
+        assert!(ByValue::from_name("Only").is_some());
+    }
 }
 
 #[cfg(test)]
-mod alias_tests {
+mod simd_switch_tests {
     #![allow(unused_imports)]
 
-    use super::alias;
+    use super::{alias, simd_switch};
 
     alias! {
-        a: { #[cfg(all())] },
-        b: { #[cfg(all())] },
-        c: { a },
-        d: { all(a, b, c) },
-        e: { any(not(a), b, all(c), #[cfg(test)]) },
-        pub f: { e },
+        std: { #[cfg(test)] },
+    }
+
+    #[test]
+    fn fallback() {
+        let selected = simd_switch! {
+            _ => { 0 }
+        };
+        assert!(selected == 0);
+    }
+
+    #[test]
+    #[cfg(target_arch = "x86_64")]
+    fn static_feature() {
+        // `sse2` is part of the `x86_64` baseline, and is always statically enabled.
+        let selected = simd_switch! {
+            "sse2" => { 1 },
+            _ => { 0 },
+        };
+        assert!(selected == 1);
+    }
+
+    #[test]
+    #[cfg(target_arch = "x86_64")]
+    fn without_detection() {
+        let selected = simd_switch! {
+            "avx2" => { 2 }
+            _ => { 0 }
+        };
+        assert!(selected == if cfg!(target_feature = "avx2") { 2 } else { 0 });
+    }
+
+    #[test]
+    #[cfg(target_arch = "x86_64")]
+    fn with_detection() {
+        let selected = simd_switch! {
+            #[detect(std)]
+            "avx2" => { 2 }
+            "sse2" => { 1 }
+            _ => { 0 }
+        };
+        let expected = if ::std::is_x86_feature_detected!("avx2") {
+            2
+        } else {
+            1
+        };
+        assert!(selected == expected);
+    }
+
+    #[test]
+    #[cfg(target_arch = "x86_64")]
+    fn detection_disabled() {
+        let selected = simd_switch! {
+            #[detect(not(std))]
+            "avx2" => { 2 }
+            _ => { 0 }
+        };
+        assert!(selected == if cfg!(target_feature = "avx2") { 2 } else { 0 });
     }
 }
 
 #[cfg(test)]
 mod switch_tests {
+    #![allow(unused_imports, clippy::needless_late_init)]
+
     use super::{disabled, enabled, switch};
 
     #[test]
@@ -847,11 +6324,183 @@ mod switch_tests {
     }
 }
 
+#[cfg(test)]
+mod switch_label_tests {
+    #![allow(unused_imports)]
+
+    use super::{alias, switch};
+
+    alias! {
+        a: { #[cfg(all())] },
+        b: { #[cfg(all())] },
+        c: { not(a) },
+    }
+
+    // Run with `--cfg crossfig_prefer="b"` to test preferences.
+    switch! {
+        "a": a => {
+            const SELECTED: char = 'a';
+        }
+        "b": b => {
+            const SELECTED: char = 'b';
+        }
+        _ => {
+            const SELECTED: char = '_';
+        }
+    }
+
+    #[test]
+    fn item() {
+        let expected = if cfg!(crossfig_prefer = "b") {
+            'b'
+        } else {
+            'a'
+        };
+        assert!(SELECTED == expected);
+    }
+
+    #[test]
+    fn expression() {
+        let selected = switch! {{
+            #[cfg(any())] => { 'x' }
+            "c": c => { 'c' }
+            "b": any(#[cfg(all())], c) => { 'b' }
+            a => { 'a' }
+        }};
+        assert!(selected == 'b');
+    }
+
+    #[test]
+    fn unlabelled() {
+        let selected = switch! {{
+            super::switch_label_tests::c => { 'c' }
+            not(a) => { 'n' }
+            _ => { '_' }
+        }};
+        assert!(selected == '_');
+    }
+}
+
+#[cfg(test)]
+mod alias_mod_tests {
+    #![allow(unused_imports, clippy::bool_comparison)]
+
+    use super::{alias, switch, Member};
+
+    alias! {
+        outer: { #[cfg(all())] },
+        #[for_each(cfg_for_each)]
+        pub(crate) mod cfg {
+            pub(crate) a: { super::outer },
+            b: { not(a) },
+            pub(crate) mod nested {
+                pub(crate) c: { super::b }
+            }
+        },
+        after: { cfg::a },
+    }
+
+    #[test]
+    fn constants() {
+        assert!(cfg::a);
+        assert!(cfg::a == cfg::a!());
+        assert!(after!());
+    }
+
+    #[test]
+    fn members() {
+        assert!(cfg::MEMBERS.len() == 2);
+        assert!(cfg::MEMBERS[0].name == "a" && cfg::MEMBERS[0].enabled);
+        assert!(
+            cfg::MEMBERS[1]
+                == Member {
+                    name: "b",
+                    condition: "not(a)",
+                    enabled: false,
+                }
+        );
+        assert!(cfg::nested::MEMBERS.len() == 1);
+    }
+
+    #[test]
+    fn for_each() {
+        let mut names = [""; 2];
+        let mut states = [false; 2];
+        let mut conditions = [""; 2];
+        let mut count = 0;
+
+        macro_rules! record {
+            ($alias:ident, $enabled:literal, $condition:expr) => {
+                names[count] = stringify!($alias);
+                states[count] = $enabled;
+                conditions[count] = $condition;
+                count += 1;
+            };
+        }
+
+        cfg_for_each!(record);
+
+        assert!(count == 2);
+        assert!(names == ["a", "b"]);
+        assert!(states == [true, false]);
+        assert!(conditions[1] == "not(a)");
+    }
+
+    #[test]
+    fn switch_arm() {
+        let selected = switch! {{
+            cfg::nested::c => { 'c' }
+            cfg::a => { 'a' }
+            _ => { '_' }
+        }};
+        assert!(selected == 'a');
+    }
+}
+
+#[cfg(test)]
+mod unstable_predicate_tests {
+    #![allow(unused_imports)]
+
+    use super::switch;
+
+    // Run with `--cfg crossfig_nightly` on a nightly compiler to test the real predicates.
+    #[test]
+    fn version() {
+        let selected = switch! {{
+            version("1.0") => { 'v' }
+            _ => { '_' }
+        }};
+        let expected = if cfg!(crossfig_nightly) { 'v' } else { '_' };
+        assert!(selected == expected);
+
+        let selected = switch! {{
+            not(version("1.0")) => { 'n' }
+            _ => { '_' }
+        }};
+        let expected = if cfg!(crossfig_nightly) { '_' } else { 'n' };
+        assert!(selected == expected);
+    }
+
+    #[test]
+    fn accessible() {
+        let selected = switch! {{
+            accessible(::core::hint::black_box) => { 'a' }
+            any(accessible(::core::mem), #[cfg(all())]) => { 'b' }
+            _ => { '_' }
+        }};
+        assert!(selected == 'b');
+    }
+}
+
 #[cfg(test)]
 mod forte_tests {
+    #![allow(unused_imports)]
+
     use super::{alias, switch};
 
     mod cfg {
+        #![allow(unused_imports)]
+
         use super::alias;
 
         alias! {
@@ -882,8 +6531,306 @@ mod forte_tests {
     }
 }
 
+#[cfg(test)]
+mod switch_unreachable_tests {
+    #![allow(unused_imports)]
+
+    use super::{alias, switch};
+
+    alias! {
+        a: { #[cfg(all())] },
+        b: { #[cfg(any())] },
+    }
+
+    switch! {
+        b => {
+            const FIRST: char = 'b';
+        }
+        not(b) => {
+            const FIRST: char = 'n';
+        }
+    }
+
+    switch! {
+        any(a, b) => {
+            const SECOND: char = 'a';
+        }
+        any(b, a) => {
+            const SECOND: char = 'b';
+        }
+    }
+
+    #[test]
+    fn similar_patterns() {
+        assert!(FIRST == 'n');
+        assert!(SECOND == 'a');
+    }
+
+    struct Associated;
+
+    // Checks must expand to nothing, as macros can't be defined within an `impl` block.
+    impl Associated {
+        switch! {
+            b => {
+                const VALUE: char = 'b';
+            }
+            not(b) => {
+                const VALUE: char = 'n';
+            }
+            _ => {
+                const VALUE: char = '_';
+            }
+        }
+    }
+
+    #[test]
+    fn within_impl() {
+        assert!(Associated::VALUE == 'n');
+    }
+
+    #[test]
+    fn labelled_duplicates() {
+        let selected = switch! {{
+            "x": a => { 'x' }
+            "y": a => { 'y' }
+            _ => { '_' }
+        }};
+        let preferred = if cfg!(crossfig_prefer = "y") {
+            'y'
+        } else {
+            'x'
+        };
+        assert!(selected == preferred);
+    }
+
+    #[test]
+    fn similar_patterns_in_expression() {
+        let selected = switch! {{
+            any(a, b) => { 'a' }
+            any(b, a) => { 'b' }
+            a => { 'x' }
+            _ => { '_' }
+        }};
+        assert!(selected == 'a');
+    }
+
+    alias! {
+        always: { all() },
+    }
+
+    impl Associated {
+        switch! {
+            b => {
+                const ALWAYS: char = 'b';
+            }
+            always => {
+                const ALWAYS: char = 'a';
+            }
+            _ => {
+                const ALWAYS: char = '_';
+            }
+        }
+    }
+
+    #[test]
+    fn unconditional_alias() {
+        let selected = always! { if { 'a' } else { '_' } };
+        assert!(selected == 'a');
+        assert!(Associated::ALWAYS == 'a');
+
+        let selected = switch! {{
+            a => { 'a' }
+            always => { 'x' }
+            _ => { '_' }
+        }};
+        assert!(selected == 'a');
+    }
+
+    #[test]
+    fn fallback_after_unconditional() {
+        let selected = switch! {{
+            b => { 'b' }
+            all() => { 'x' }
+            _ => { '_' }
+        }};
+        assert!(selected == 'x');
+    }
+}
+
+#[cfg(test)]
+mod switch_exhaustive_tests {
+    #![allow(unused_imports)]
+
+    use super::{alias, switch_exhaustive};
+
+    alias! {
+        a: { #[cfg(all())] },
+        b: { not(a) },
+    }
+
+    switch_exhaustive! {
+        b => {
+            const SELECTED: char = 'b';
+        }
+        "a": super::switch_exhaustive_tests::a => {
+            const SELECTED: char = 'a';
+        }
+    }
+
+    #[test]
+    fn item() {
+        assert!(SELECTED == 'a');
+    }
+
+    #[test]
+    fn expression() {
+        let selected = switch_exhaustive! {{
+            b => { 'b' }
+            all(a, #[cfg(test)]) => { 'a' }
+        }};
+        assert!(selected == 'a');
+
+        let selected = switch_exhaustive! {{
+            b => { 'b' }
+            _ => { '_' }
+        }};
+        assert!(selected == '_');
+    }
+}
+
+#[cfg(test)]
+mod switch_selected_tests {
+    #![allow(unused_imports)]
+
+    use core::option::Option::Some;
+    use std::string::ToString;
+
+    use super::{alias, switch_selected, Selected};
+
+    alias! {
+        a: { #[cfg(all())] },
+        b: { not(a) },
+    }
+
+    switch_selected! {
+        const FIRST;
+        b => {}
+        "a": a => {}
+        _ => {}
+    }
+
+    // Run with `--cfg crossfig_prefer="q"` to test preferences.
+    switch_selected! {
+        const PREFERRED;
+        "p": a => {}
+        "q": a => {}
+        _ => {}
+    }
+
+    switch_selected! {
+        pub(crate) const FALLBACK;
+        #[cfg(any())] => {}
+        b => {}
+        _ => {}
+    }
+
+    #[test]
+    fn labelled() {
+        assert!(
+            FIRST
+                == Selected {
+                    index: 1,
+                    label: Some("a"),
+                    condition: "a",
+                }
+        );
+        assert!(FIRST.to_string() == "a (arm 1, `a`)");
+    }
+
+    #[test]
+    fn preferred() {
+        let (index, label) = if cfg!(crossfig_prefer = "q") {
+            (1, "q")
+        } else {
+            (0, "p")
+        };
+        assert!(PREFERRED.index == index);
+        assert!(PREFERRED.label == Some(label));
+    }
+
+    #[test]
+    fn wildcard() {
+        assert!(FALLBACK.index == 2);
+        assert!(FALLBACK.label.is_none());
+        assert!(FALLBACK.to_string() == "arm 2, `_`");
+    }
+
+    #[test]
+    fn statement() {
+        switch_selected! {
+            const INNER;
+            #[cfg(all())] => {
+                let value = 'a';
+            }
+            _ => {
+                let value = '_';
+            }
+        }
+
+        assert!(value == 'a');
+        assert!(INNER.condition == "#[cfg(all())]");
+    }
+}
+
+#[cfg(test)]
+mod switch_const_tests {
+    #![allow(unused_imports)]
+
+    use super::{alias, switch_const};
+
+    alias! {
+        a: { #[cfg(all())] },
+        b: { not(a) },
+    }
+
+    const FIRST: usize = switch_const! {
+        a => 1,
+        _ => 2,
+    };
+
+    const SKIPPED: usize = switch_const! {
+        b => 1,
+        #[cfg(any())] => 2,
+        not(a) => 3,
+        _ => 4
+    };
+
+    const META: &str = switch_const! {
+        #[cfg(test)] => "test",
+        _ => "not test",
+    };
+
+    const OPERATION: i32 = switch_const! {
+        any(b, all(a, #[cfg(test)])) => -1,
+        _ => 1,
+    };
+
+    const WILDCARD: () = switch_const! { _ => () };
+
+    #[test]
+    fn selected() {
+        assert!(FIRST == 1);
+        assert!(SKIPPED == 4);
+        assert!(META == "test");
+        assert!(OPERATION == -1);
+        let () = WILDCARD;
+    }
+}
+
 #[cfg(test)]
 mod switch_as_value_tests {
+    #![allow(unused_imports)]
+
     use super::switch;
 
     const PASSED: bool = switch! {
@@ -895,3 +6842,41 @@ mod switch_as_value_tests {
         assert!(PASSED);
     }
 }
+
+#[cfg(test)]
+mod alias_table_tests {
+    #![allow(unused_imports)]
+
+    use super::alias_table;
+
+    alias_table! {
+        macro_rules! table;
+
+        /// Always active.
+        a: { #[cfg(all())] },
+        #[allow(unused_imports)]
+        /// Never active.
+        ///
+        /// Spans multiple lines.
+        b: { not(a) },
+        c: { any(a, b) }
+    }
+
+    /// Documentation built from [`table`].
+    #[doc = table!()]
+    const _DOCUMENTED: () = ();
+
+    #[test]
+    fn table() {
+        assert!(
+            table!()
+                == concat!(
+                    "| Alias | Description | Condition | State |\n",
+                    "| --- | --- | --- | --- |\n",
+                    "| `a` | Always active. | `#[cfg(all())]` | enabled |\n",
+                    "| `b` | Never active. Spans multiple lines. | `not(a)` | disabled |\n",
+                    "| `c` | | `any(a, b)` | enabled |\n",
+                )
+        );
+    }
+}
@@ -0,0 +1,89 @@
+//! Exports aliases for testing `#[macro_use] extern crate ...` consumers.
+//!
+//! The `on` and `off` modules define an alias named `enabled_here`, which would clash if exported from the crate
+//! root under that name.
+
+#![no_std]
+
+#[macro_use]
+extern crate crossfig;
+
+/// Aliases which are always enabled.
+pub mod on {
+    alias! {
+        #[marker_trait(
+            EnabledHere,
+            message = "this requires `enabled_here`",
+            note = "`enabled_here` is always enabled",
+        )]
+        /// Always enabled.
+        #[macro_export(legacy_on)]
+        pub enabled_here: { #[cfg(all())] },
+    }
+}
+
+/// Aliases which are never enabled.
+pub mod off {
+    alias! {
+        #[marker_trait(EnabledHere, message = "this requires `enabled_here`")]
+        /// Never enabled.
+        #[macro_export(legacy_off)]
+        pub enabled_here: { not(super::on::enabled_here) },
+    }
+}
+
+/// Can only be called while [`on::enabled_here`] is enabled.
+pub fn requires_on<T: on::EnabledHere>(value: T) -> T {
+    value
+}
+
+/// Can only be called while [`off::enabled_here`] is enabled.
+pub fn requires_off<T: off::EnabledHere>(value: T) -> T {
+    value
+}
+
+/// Aliases which depend on whether tests are being compiled.
+pub mod test {
+    alias! {
+        /// Evaluated while compiling this crate, which is never a test.
+        #[macro_export(legacy_defined_in_test)]
+        pub defined_in_test: { #[cfg(test)] },
+    }
+}
+
+// Deprecated and call-site aliases are exported from the crate root, so must be defined there.
+alias! {
+    /// Never enabled, and deprecated.
+    #[deprecated(note = "use `legacy_off` instead")]
+    #[macro_export(legacy_off_deprecated)]
+    pub deprecated_here: { off::enabled_here },
+    /// Evaluated while compiling the consumer.
+    #[call_site]
+    #[macro_export(legacy_used_in_test)]
+    pub used_in_test: { #[cfg(test)] },
+}
+
+alias! {
+    /// Aliases defined as a module.
+    #[for_each(legacy_cfg_for_each)]
+    pub mod cfg {
+        /// Always enabled.
+        pub always: { super::on::enabled_here },
+        /// Never enabled.
+        pub never: { not(always) },
+    }
+}
+
+features! {
+    /// Enabled by default.
+    #[macro_export(legacy_on_by_default)]
+    pub on_by_default = "on_by_default",
+    pub renamed = "off_by_default",
+}
+
+alias! {
+    /// Names a crate which doesn't exist, behind a feature which isn't enabled.
+    pub missing_dependency: { dep(feature = "off_by_default", missing::std) },
+    /// Names an alias of a dependency, behind a feature which is enabled.
+    pub present_dependency: { dep(feature = "on_by_default", crossfig::enabled) },
+}
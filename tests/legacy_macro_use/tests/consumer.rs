@@ -0,0 +1,84 @@
+#![deny(deprecated)]
+#![allow(clippy::assertions_on_constants)]
+
+#[macro_use]
+extern crate crossfig;
+#[macro_use]
+extern crate legacy_macro_use;
+
+#[test]
+fn as_bool() {
+    assert!(legacy_on!());
+    assert!(!legacy_off!());
+}
+
+#[test]
+fn as_switch_arm() {
+    switch! {
+        legacy_off => {
+            compile_error!("expected skip");
+        }
+        legacy_on => {
+            let _a = ();
+        }
+        _ => {
+            compile_error!("expected skip");
+        }
+    }
+}
+
+#[test]
+fn by_path() {
+    assert!(legacy_macro_use::on::enabled_here!());
+    assert!(!legacy_macro_use::off::enabled_here!());
+}
+
+#[test]
+#[allow(deprecated)]
+fn deprecated() {
+    assert!(!legacy_off_deprecated!());
+}
+
+#[test]
+fn call_site() {
+    assert!(!legacy_defined_in_test!());
+    assert!(legacy_used_in_test!());
+    assert!(legacy_macro_use::used_in_test!());
+}
+
+#[test]
+fn marker_trait() {
+    assert_eq!(legacy_macro_use::requires_on(1), 1);
+}
+
+#[test]
+fn module() {
+    assert!(legacy_macro_use::cfg::always!());
+    assert!(legacy_macro_use::cfg::always);
+    assert!(!legacy_macro_use::cfg::never);
+    assert!(legacy_macro_use::cfg::MEMBERS.len() == 2);
+}
+
+#[test]
+fn for_each() {
+    macro_rules! check {
+        ($alias:ident, $enabled:literal, $condition:expr) => {
+            assert_eq!($enabled, legacy_macro_use::cfg::$alias);
+        };
+    }
+
+    legacy_cfg_for_each!(check);
+}
+
+#[test]
+fn features() {
+    assert!(legacy_macro_use::on_by_default!());
+    assert!(legacy_on_by_default!());
+    assert!(!legacy_macro_use::renamed!());
+}
+
+#[test]
+fn optional_dependency() {
+    assert!(!legacy_macro_use::missing_dependency!());
+    assert!(legacy_macro_use::present_dependency!());
+}